@@ -0,0 +1,131 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+impl Instruction {
+    pub fn parse(line: &str) -> Instruction {
+        match line.split(' ').collect::<Vec<&str>>()[..] {
+            ["noop"] => Instruction::Noop,
+            ["addx", value] => Instruction::Addx(value.parse().unwrap()),
+            _ => panic!("Invalid instruction: {}", line),
+        }
+    }
+
+    pub fn cycles(&self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+}
+
+// Anything attached to the CPU, ticked during every cycle with the value of X.
+pub trait Device {
+    fn tick(&mut self, cycle: usize, x: i64);
+}
+
+impl<F: FnMut(usize, i64)> Device for F {
+    fn tick(&mut self, cycle: usize, x: i64) {
+        self(cycle, x)
+    }
+}
+
+pub struct Cpu {
+    pub x: i64,
+    pub cycle: usize,
+}
+
+impl Cpu {
+    pub fn new() -> Cpu {
+        Cpu { x: 1, cycle: 0 }
+    }
+
+    pub fn execute(&mut self, instruction: Instruction, device: &mut impl Device) {
+        for _ in 0..instruction.cycles() {
+            self.cycle += 1;
+            device.tick(self.cycle, self.x);
+        }
+        if let Instruction::Addx(value) = instruction {
+            self.x += value;
+        }
+    }
+
+    pub fn run(&mut self, program: &[Instruction], device: &mut impl Device) {
+        for instruction in program {
+            self.execute(*instruction, device);
+        }
+    }
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Cpu::new()
+    }
+}
+
+pub struct SignalProbe {
+    pub cycles: Vec<usize>,
+    pub strength: i64,
+}
+
+impl Device for SignalProbe {
+    fn tick(&mut self, cycle: usize, x: i64) {
+        if self.cycles.contains(&cycle) {
+            self.strength += cycle as i64 * x;
+        }
+    }
+}
+
+pub struct Crt {
+    pub width: usize,
+    pub pixels: Vec<bool>,
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Crt {
+        Crt {
+            width,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn render(&self) -> String {
+        self.pixels
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|lit| if *lit { '#' } else { '.' })
+                    .chain(std::iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect()
+    }
+}
+
+impl Device for Crt {
+    fn tick(&mut self, cycle: usize, x: i64) {
+        let position = (cycle - 1) % self.pixels.len();
+        let column = (position % self.width) as i64;
+        self.pixels[position] = (column - x).abs() <= 1;
+    }
+}
+
+pub fn get_program(lines: &[String]) -> Vec<Instruction> {
+    lines.iter().map(|line| Instruction::parse(line)).collect()
+}
+
+#[test]
+fn example() {
+    let program = get_program(&[
+        String::from("noop"),
+        String::from("addx 3"),
+        String::from("addx -5"),
+    ]);
+    let mut values = Vec::new();
+    let mut cpu = Cpu::new();
+    cpu.run(&program, &mut |cycle, x| values.push((cycle, x)));
+    assert_eq!(values, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+    assert_eq!(cpu.x, -1);
+}
@@ -0,0 +1,366 @@
+use crate::utils::big_uint::BigUint;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Old,
+    Constant(u64),
+    Add(Box<Expr>, Box<Expr>),
+    Multiply(Box<Expr>, Box<Expr>),
+}
+
+fn get_tokens(s: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(word);
+            word = String::new();
+        }
+        if !c.is_whitespace() {
+            tokens.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+impl Expr {
+    // Parses the right-hand side of `new = ...`, with `*` binding tighter than `+`.
+    pub fn parse(s: &str) -> Expr {
+        let tokens = get_tokens(s);
+        let (expr, rest) = Expr::parse_sum(&tokens);
+        assert!(rest.is_empty(), "Unexpected '{}' in '{}'", rest[0], s);
+        expr
+    }
+
+    fn parse_sum(tokens: &[String]) -> (Expr, &[String]) {
+        let (mut expr, mut rest) = Expr::parse_product(tokens);
+        while rest.first().is_some_and(|t| t == "+") {
+            let (right, next) = Expr::parse_product(&rest[1..]);
+            expr = Expr::Add(Box::new(expr), Box::new(right));
+            rest = next;
+        }
+        (expr, rest)
+    }
+
+    fn parse_product(tokens: &[String]) -> (Expr, &[String]) {
+        let (mut expr, mut rest) = Expr::parse_term(tokens);
+        while rest.first().is_some_and(|t| t == "*") {
+            let (right, next) = Expr::parse_term(&rest[1..]);
+            expr = Expr::Multiply(Box::new(expr), Box::new(right));
+            rest = next;
+        }
+        (expr, rest)
+    }
+
+    fn parse_term(tokens: &[String]) -> (Expr, &[String]) {
+        match tokens.first().map(|t| t.as_str()) {
+            Some("old") => (Expr::Old, &tokens[1..]),
+            Some("(") => {
+                let (expr, rest) = Expr::parse_sum(&tokens[1..]);
+                assert!(rest.first().is_some_and(|t| t == ")"), "Missing ')'");
+                (expr, &rest[1..])
+            }
+            Some(number) => (Expr::Constant(number.parse().unwrap()), &tokens[1..]),
+            None => panic!("Unexpected end of expression"),
+        }
+    }
+
+    pub fn evaluate<W: Worry>(&self, old: &W) -> W {
+        match self {
+            Expr::Old => old.clone(),
+            Expr::Constant(value) => W::from_u64(*value),
+            Expr::Add(a, b) => a.evaluate(old).add(&b.evaluate(old)),
+            Expr::Multiply(a, b) => a.evaluate(old).multiply(&b.evaluate(old)),
+        }
+    }
+}
+
+// A number type that worry levels can be tracked in.
+pub trait Worry: Clone {
+    fn from_u64(value: u64) -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn multiply(&self, other: &Self) -> Self;
+    fn divide(&self, divisor: u64) -> Self;
+    fn remainder(&self, divisor: u64) -> u64;
+}
+
+impl Worry for u64 {
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self.checked_add(*other).expect("Worry level overflowed")
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        self.checked_mul(*other).expect("Worry level overflowed")
+    }
+
+    fn divide(&self, divisor: u64) -> Self {
+        self / divisor
+    }
+
+    fn remainder(&self, divisor: u64) -> u64 {
+        self % divisor
+    }
+}
+
+impl Worry for BigUint {
+    fn from_u64(value: u64) -> Self {
+        BigUint::from_u64(value)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        BigUint::add(self, other)
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        BigUint::multiply(self, other)
+    }
+
+    fn divide(&self, divisor: u64) -> Self {
+        self.divide_u64(divisor).0
+    }
+
+    fn remainder(&self, divisor: u64) -> u64 {
+        self.divide_u64(divisor).1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relief {
+    DivideBy(u64),
+    Modulo(u64),
+    Exact,
+}
+
+impl Relief {
+    // Reducing modulo the LCM of every divisor keeps all the tests' outcomes.
+    pub fn lcm(monkeys: &[Monkey]) -> Relief {
+        Relief::Modulo(
+            monkeys
+                .iter()
+                .fold(1, |lcm, m| lcm / gcd(lcm, m.divisor) * m.divisor),
+        )
+    }
+
+    pub fn apply<W: Worry>(&self, worry: W) -> W {
+        match self {
+            Relief::DivideBy(divisor) => worry.divide(*divisor),
+            Relief::Modulo(modulus) => W::from_u64(worry.remainder(*modulus)),
+            Relief::Exact => worry,
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub operation: Expr,
+    pub divisor: u64,
+    pub if_true: usize,
+    pub if_false: usize,
+}
+
+fn get_value<'a>(line: Option<&'a String>, prefix: &str) -> &'a str {
+    let line = line
+        .unwrap_or_else(|| panic!("Missing '{}'", prefix))
+        .trim();
+    line.strip_prefix(prefix)
+        .unwrap_or_else(|| panic!("Expected '{}' but found '{}'", prefix, line))
+        .trim()
+}
+
+fn get_monkey(lines: &[String]) -> Monkey {
+    let mut lines = lines.iter();
+    get_value(lines.next(), "Monkey");
+    let items = get_value(lines.next(), "Starting items:");
+    Monkey {
+        items: items
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| s.trim().parse::<u64>().unwrap())
+            .collect(),
+        operation: Expr::parse(get_value(lines.next(), "Operation: new =")),
+        divisor: get_value(lines.next(), "Test: divisible by")
+            .parse()
+            .unwrap(),
+        if_true: get_value(lines.next(), "If true: throw to monkey")
+            .parse()
+            .unwrap(),
+        if_false: get_value(lines.next(), "If false: throw to monkey")
+            .parse()
+            .unwrap(),
+    }
+}
+
+pub fn get_monkeys(lines: &[String]) -> Vec<Monkey> {
+    let monkeys = lines
+        .split(|line| line.trim().is_empty())
+        .filter(|block| !block.is_empty())
+        .map(get_monkey)
+        .collect::<Vec<Monkey>>();
+    for monkey in monkeys.iter() {
+        assert!(
+            monkey.if_true < monkeys.len() && monkey.if_false < monkeys.len(),
+            "Monkey throws to a monkey that does not exist"
+        );
+    }
+    monkeys
+}
+
+pub struct Troop<W: Worry> {
+    pub items: Vec<Vec<W>>,
+    pub inspections: Vec<usize>,
+}
+
+impl<W: Worry> Troop<W> {
+    pub fn new(monkeys: &[Monkey]) -> Troop<W> {
+        Troop {
+            items: monkeys
+                .iter()
+                .map(|m| m.items.iter().map(|i| W::from_u64(*i)).collect())
+                .collect(),
+            inspections: vec![0; monkeys.len()],
+        }
+    }
+
+    pub fn play_round(&mut self, monkeys: &[Monkey], relief: Relief) {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            self.inspections[i] += self.items[i].len();
+            for item in std::mem::take(&mut self.items[i]) {
+                let item = relief.apply(monkey.operation.evaluate(&item));
+                if item.remainder(monkey.divisor) == 0 {
+                    self.items[monkey.if_true].push(item);
+                } else {
+                    self.items[monkey.if_false].push(item);
+                }
+            }
+        }
+    }
+}
+
+// Returns how many items each monkey inspected.
+pub fn simulate<W: Worry>(monkeys: &[Monkey], rounds: usize, relief: Relief) -> Vec<usize> {
+    let mut troop = Troop::<W>::new(monkeys);
+    for _ in 0..rounds {
+        troop.play_round(monkeys, relief);
+    }
+    troop.inspections
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub round: usize,
+    pub exact: Vec<usize>,
+    pub reduced: Vec<usize>,
+}
+
+// Runs the rules in `exact` with arbitrary precision next to a `u64` simulation
+// using `reduced`, and reports the first round where inspection counts differ.
+pub fn cross_check(
+    monkeys: &[Monkey],
+    rounds: usize,
+    exact: Relief,
+    reduced: Relief,
+) -> Result<Vec<usize>, Mismatch> {
+    let mut exact_troop = Troop::<BigUint>::new(monkeys);
+    let mut reduced_troop = Troop::<u64>::new(monkeys);
+    for round in 1..=rounds {
+        exact_troop.play_round(monkeys, exact);
+        reduced_troop.play_round(monkeys, reduced);
+        if exact_troop.inspections != reduced_troop.inspections {
+            return Err(Mismatch {
+                round,
+                exact: exact_troop.inspections,
+                reduced: reduced_troop.inspections,
+            });
+        }
+    }
+    Ok(exact_troop.inspections)
+}
+
+// Product of the two highest inspection counts, or of however many monkeys
+// there are if fewer than two.
+pub fn get_monkey_business(inspections: &[usize]) -> usize {
+    let mut inspections = inspections.to_vec();
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    inspections.iter().take(2).product()
+}
+
+#[cfg(test)]
+fn example() -> Vec<Monkey> {
+    let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+    get_monkeys(&input.lines().map(String::from).collect::<Vec<String>>())
+}
+
+#[test]
+fn example_monkeys() {
+    let monkeys = example();
+    let inspections = simulate::<u64>(&monkeys, 20, Relief::DivideBy(3));
+    assert_eq!(get_monkey_business(&inspections), 10605);
+    let inspections = simulate::<u64>(&monkeys, 10000, Relief::lcm(&monkeys));
+    assert_eq!(get_monkey_business(&inspections), 2713310158);
+    assert_eq!(get_monkey_business(&inspections[..1]), 52166);
+}
+
+#[test]
+fn exact_worry() {
+    let monkeys = example();
+    let inspections = cross_check(&monkeys, 12, Relief::Exact, Relief::lcm(&monkeys)).unwrap();
+    assert_eq!(
+        inspections,
+        simulate::<u64>(&monkeys, 12, Relief::lcm(&monkeys))
+    );
+    let mismatch = cross_check(&monkeys, 20, Relief::DivideBy(3), Relief::lcm(&monkeys));
+    assert_eq!(mismatch.unwrap_err().round, 1);
+}
+
+#[test]
+fn expressions() {
+    let expr = Expr::parse("old * (old + 2) + 3 * old");
+    assert_eq!(expr.evaluate(&5u64), 50);
+    assert_eq!(Expr::parse("old + old").evaluate(&7u64), 14);
+}
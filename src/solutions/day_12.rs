@@ -0,0 +1,148 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+pub struct HeightMap {
+    pub width: usize,
+    pub heights: Vec<u8>,
+    pub start: usize,
+    pub end: usize,
+}
+
+pub struct Paths {
+    pub distances: Vec<Option<usize>>,
+    pub next: Vec<Option<usize>>,
+}
+
+impl HeightMap {
+    pub fn parse(lines: &[String]) -> HeightMap {
+        let width = lines[0].len();
+        let cells = lines.iter().flat_map(|l| l.bytes()).collect::<Vec<u8>>();
+        assert_eq!(cells.len(), width * lines.len(), "Map must be rectangular");
+        let find = |marker: u8| {
+            cells
+                .iter()
+                .position(|c| *c == marker)
+                .unwrap_or_else(|| panic!("Missing '{}' marker", marker as char))
+        };
+        HeightMap {
+            width,
+            start: find(b'S'),
+            end: find(b'E'),
+            heights: cells
+                .iter()
+                .map(|c| match c {
+                    b'S' => 0,
+                    b'E' => 25,
+                    b'a'..=b'z' => c - b'a',
+                    _ => panic!("Invalid height '{}'", *c as char),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        (index / self.width, index % self.width)
+    }
+
+    fn get_neighbours(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let (row, column) = self.position(index);
+        let rows = self.heights.len() / self.width;
+        [
+            (row > 0).then(|| index - self.width),
+            (row + 1 < rows).then(|| index + self.width),
+            (column > 0).then(|| index - 1),
+            (column + 1 < self.width).then(|| index + 1),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    // Breadth-first search backwards from `E`, so one pass gives the distance
+    // and next step from every cell that can reach it.
+    pub fn get_paths_to_end(&self) -> Paths {
+        let mut paths = Paths {
+            distances: vec![None; self.heights.len()],
+            next: vec![None; self.heights.len()],
+        };
+        paths.distances[self.end] = Some(0);
+        let mut queue = VecDeque::from([self.end]);
+        while let Some(current) = queue.pop_front() {
+            let distance = paths.distances[current].unwrap();
+            for neighbour in self.get_neighbours(current) {
+                if paths.distances[neighbour].is_none()
+                    && self.heights[current] <= self.heights[neighbour] + 1
+                {
+                    paths.distances[neighbour] = Some(distance + 1);
+                    paths.next[neighbour] = Some(current);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        paths
+    }
+
+    // Shortest route to `E` from any of the cells matching `is_start`.
+    pub fn get_route(&self, paths: &Paths, is_start: impl Fn(usize) -> bool) -> Option<Vec<usize>> {
+        let start = (0..self.heights.len())
+            .filter(|i| is_start(*i))
+            .filter(|i| paths.distances[*i].is_some())
+            .min_by_key(|i| paths.distances[*i])?;
+        let mut route = vec![start];
+        while let Some(next) = paths.next[*route.last().unwrap()] {
+            route.push(next);
+        }
+        Some(route)
+    }
+
+    pub fn render_route(&self, route: &[usize]) -> String {
+        let mut cells = self
+            .heights
+            .iter()
+            .map(|h| (b'a' + h) as char)
+            .collect::<Vec<char>>();
+        cells[self.start] = 'S';
+        cells[self.end] = 'E';
+        for step in route.windows(2) {
+            let (from, to) = (self.position(step[0]), self.position(step[1]));
+            cells[step[0]] = match (from.0.cmp(&to.0), from.1.cmp(&to.1)) {
+                (Ordering::Less, _) => 'v',
+                (Ordering::Greater, _) => '^',
+                (_, Ordering::Less) => '>',
+                _ => '<',
+            };
+        }
+        cells
+            .chunks(self.width)
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+#[test]
+fn example() {
+    let lines = ["Sabqponm", "abcryxxl", "accszExk", "acctuvwj", "abdefghi"]
+        .iter()
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>();
+    let map = HeightMap::parse(&lines);
+    let paths = map.get_paths_to_end();
+    let route = map.get_route(&paths, |i| i == map.start).unwrap();
+    assert_eq!(route.len() - 1, 31);
+    assert_eq!(map.position(*route.last().unwrap()), (2, 5));
+    let scenic = map.get_route(&paths, |i| map.heights[i] == 0).unwrap();
+    assert_eq!(scenic.len() - 1, 29);
+    let rendered = map.render_route(&route);
+    assert_eq!(rendered.lines().count(), 5);
+    assert_eq!(rendered.chars().filter(|c| "<>^v".contains(*c)).count(), 31);
+
+    // A single column climbing from S through b..=y to E.
+    let column = std::iter::once(String::from("S"))
+        .chain((b'b'..=b'y').map(|c| (c as char).to_string()))
+        .chain(std::iter::once(String::from("E")))
+        .collect::<Vec<String>>();
+    let map = HeightMap::parse(&column);
+    let route = map
+        .get_route(&map.get_paths_to_end(), |i| i == map.start)
+        .unwrap();
+    assert_eq!(map.render_route(&route), "v\n".repeat(25) + "E\n");
+}
@@ -0,0 +1,318 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+use crate::utils::random::XorShift;
+
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Integer(u64),
+    List(Vec<Packet>),
+}
+
+// Builds a packet from a literal, e.g. `packet!([1, [2, 3], []])`.
+#[macro_export]
+macro_rules! packet {
+    ([$($item:tt),* $(,)?]) => {
+        $crate::solutions::day_13::Packet::List(vec![$($crate::packet!($item)),*])
+    };
+    ($value:expr) => {
+        $crate::solutions::day_13::Packet::Integer($value)
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected {} at position {}",
+            self.expected, self.position
+        )
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn error(&self, expected: &'static str) -> ParseError {
+        ParseError {
+            position: self.position,
+            expected,
+        }
+    }
+
+    fn parse_packet(&mut self) -> Result<Packet, ParseError> {
+        match self.peek() {
+            Some(b'[') => self.parse_list(),
+            Some(b'0'..=b'9') => self.parse_integer(),
+            _ => Err(self.error("'[' or a digit")),
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Packet, ParseError> {
+        self.position += 1;
+        let mut items = Vec::new();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Packet::List(items));
+        }
+        loop {
+            items.push(self.parse_packet()?);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Packet::List(items));
+                }
+                _ => return Err(self.error("',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_integer(&mut self) -> Result<Packet, ParseError> {
+        let start = self.position;
+        let mut value: u64 = 0;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((digit - b'0') as u64))
+                .ok_or(ParseError {
+                    position: start,
+                    expected: "an integer that fits in 64 bits",
+                })?;
+            self.position += 1;
+        }
+        Ok(Packet::Integer(value))
+    }
+}
+
+impl Packet {
+    // Parses a packet straight from the input bytes, without copying them.
+    pub fn parse(bytes: &[u8]) -> Result<Packet, ParseError> {
+        let mut parser = Parser { bytes, position: 0 };
+        let packet = parser.parse_packet()?;
+        match parser.peek() {
+            None => Ok(packet),
+            Some(_) => Err(parser.error("end of packet")),
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Packet::parse(s.as_bytes())
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Packet::Integer(value) => write!(f, "{}", value),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(a), Packet::Integer(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::List(a), Packet::Integer(_)) => a.as_slice().cmp(std::slice::from_ref(other)),
+            (Packet::Integer(_), Packet::List(b)) => std::slice::from_ref(self).cmp(b.as_slice()),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Equality follows the ordering, so `[1]` and `1` are equal packets.
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Packet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Packet::Integer(value) => serializer.serialize_u64(*value),
+            Packet::List(items) => serializer.collect_seq(items),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct PacketVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for PacketVisitor {
+    type Value = Packet;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a non-negative integer or an array of packets")
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Packet, E> {
+        Ok(Packet::Integer(value))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Packet, E> {
+        u64::try_from(value)
+            .map(Packet::Integer)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Packet, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Packet::List(items))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Packet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(PacketVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<&Packet> for serde_json::Value {
+    fn from(packet: &Packet) -> Self {
+        match packet {
+            Packet::Integer(value) => serde_json::Value::from(*value),
+            Packet::List(items) => serde_json::Value::Array(items.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<&serde_json::Value> for Packet {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        serde::Deserialize::deserialize(value)
+    }
+}
+
+// Reads a stream of JSON packets, which may be spread over lines or separated
+// by any whitespace.
+#[cfg(feature = "serde")]
+pub fn get_packets_from_json(text: &str) -> Result<Vec<Packet>, serde_json::Error> {
+    serde_json::Deserializer::from_str(text)
+        .into_iter::<Packet>()
+        .collect()
+}
+
+pub fn get_packets(lines: &[String]) -> Vec<Packet> {
+    lines
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn round_trip() {
+    let packet = packet!([1, [2, [3, [4, [5, 6, 7]]]], 8, 9]);
+    assert_eq!(packet.to_string(), "[1,[2,[3,[4,[5,6,7]]]],8,9]");
+    assert_eq!(
+        packet.to_string().parse::<Packet>().unwrap().to_string(),
+        packet.to_string()
+    );
+    assert!(packet!([[1], [2, 3, 4]]) < packet!([[1], 4]));
+    assert_eq!(
+        "[1,[2,]]".parse::<Packet>().unwrap_err(),
+        ParseError {
+            position: 6,
+            expected: "'[' or a digit"
+        }
+    );
+    assert_eq!("[1]]".parse::<Packet>().unwrap_err().position, 3);
+}
+
+#[cfg(test)]
+fn get_random_packet(rng: &mut XorShift, depth: usize) -> Packet {
+    let value = rng.next_u64();
+    if depth == 0 || value.is_multiple_of(3) {
+        return Packet::Integer(value % 4);
+    }
+    let length = (value >> 8) % 4;
+    Packet::List(
+        (0..length)
+            .map(|_| get_random_packet(rng, depth - 1))
+            .collect(),
+    )
+}
+
+#[test]
+fn total_order() {
+    let mut rng = XorShift::new(0x2022_1213);
+    let packets = (0..60)
+        .map(|_| get_random_packet(&mut rng, 4))
+        .collect::<Vec<Packet>>();
+    for a in packets.iter() {
+        assert_eq!(
+            a.to_string().parse::<Packet>().unwrap().to_string(),
+            a.to_string()
+        );
+        for b in packets.iter() {
+            assert_eq!(a.cmp(b), b.cmp(a).reverse());
+            assert_eq!(a == b, a.cmp(b) == Ordering::Equal);
+            for c in packets.iter() {
+                if a <= b && b <= c {
+                    assert!(a <= c, "{} <= {} <= {} but not {} <= {}", a, b, c, a, c);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json() {
+    let packets = get_packets_from_json("[1, [2,\n  []]]\n\n\t[ 3 ]").unwrap();
+    assert_eq!(packets[0].to_string(), "[1,[2,[]]]");
+    assert_eq!(packets[1].to_string(), "[3]");
+    let value = serde_json::Value::from(&packets[0]);
+    assert_eq!(value, serde_json::json!([1, [2, []]]));
+    assert_eq!(Packet::try_from(&value).unwrap().to_string(), "[1,[2,[]]]");
+    assert_eq!(serde_json::to_string(&packets[1]).unwrap(), "[3]");
+    assert!(get_packets_from_json("[-1]").is_err());
+    assert!(get_packets_from_json("[\"a\"]").is_err());
+}
@@ -0,0 +1,208 @@
+const SOURCE: (usize, usize) = (500, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+pub struct Cave {
+    pub min_x: usize,
+    pub width: usize,
+    pub height: usize,
+    pub tiles: Vec<Tile>,
+    pub floor: Option<usize>,
+    pub grains: usize,
+    path: Vec<(usize, usize)>,
+}
+
+fn get_paths(lines: &[String]) -> Vec<Vec<(usize, usize)>> {
+    lines
+        .iter()
+        .map(|line| {
+            line.split(" -> ")
+                .map(|point| {
+                    let (x, y) = point.split_once(',').unwrap();
+                    (x.parse().unwrap(), y.parse().unwrap())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl Cave {
+    // With a floor, sand can spread at most one column per row either side of
+    // the source, so the grid only needs to cover that triangle, clipped at
+    // x = 0.
+    pub fn parse(lines: &[String], has_floor: bool) -> Cave {
+        let paths = get_paths(lines);
+        let points = paths.iter().flatten().chain(std::iter::once(&SOURCE));
+        let max_y = points.clone().map(|p| p.1).max().unwrap();
+        let mut min_x = points.clone().map(|p| p.0).min().unwrap().saturating_sub(1);
+        let mut max_x = points.map(|p| p.0).max().unwrap() + 1;
+        let floor = has_floor.then_some(max_y + 2);
+        let height = floor.unwrap_or(max_y + 1);
+        if let Some(floor) = floor {
+            min_x = min_x.min(SOURCE.0.saturating_sub(floor));
+            max_x = max_x.max(SOURCE.0 + floor);
+        }
+        let width = max_x - min_x + 1;
+        let mut cave = Cave {
+            min_x,
+            width,
+            height,
+            tiles: vec![Tile::Air; width * height],
+            floor,
+            grains: 0,
+            path: Vec::new(),
+        };
+        for path in paths {
+            for segment in path.windows(2) {
+                let (from, to) = (segment[0], segment[1]);
+                for x in from.0.min(to.0)..=from.0.max(to.0) {
+                    for y in from.1.min(to.1)..=from.1.max(to.1) {
+                        let index = cave.index(x, y).unwrap();
+                        cave.tiles[index] = Tile::Rock;
+                    }
+                }
+            }
+        }
+        cave
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.min_x || x >= self.min_x + self.width || y >= self.height {
+            return None;
+        }
+        Some(y * self.width + x - self.min_x)
+    }
+
+    // Drops one grain, resuming from where the previous grain's path was last
+    // free. Returns false once grains fall into the abyss or block the source.
+    pub fn drop_grain(&mut self) -> bool {
+        if self.path.is_empty() {
+            if self.tiles[self.index(SOURCE.0, SOURCE.1).unwrap()] != Tile::Air {
+                return false;
+            }
+            self.path.push(SOURCE);
+        }
+        loop {
+            let (x, y) = *self.path.last().unwrap();
+            if self.floor == Some(y + 1) {
+                break;
+            }
+            let mut next = None;
+            for candidate in [Some(x), x.checked_sub(1), Some(x + 1)] {
+                let candidate = match candidate {
+                    Some(column) => (column, y + 1),
+                    None => return false,
+                };
+                match self.index(candidate.0, candidate.1) {
+                    None => return false,
+                    Some(index) if self.tiles[index] == Tile::Air => {
+                        next = Some(candidate);
+                        break;
+                    }
+                    Some(_) => {}
+                }
+            }
+            match next {
+                Some(next) => self.path.push(next),
+                None => break,
+            }
+        }
+        let (x, y) = self.path.pop().unwrap();
+        let index = self.index(x, y).unwrap();
+        self.tiles[index] = Tile::Sand;
+        self.grains += 1;
+        true
+    }
+
+    // Drops up to `count` grains and returns how many came to rest.
+    pub fn drop_grains(&mut self, count: usize) -> usize {
+        (0..count).take_while(|_| self.drop_grain()).count()
+    }
+
+    pub fn fill(&mut self) -> usize {
+        while self.drop_grain() {}
+        self.grains
+    }
+
+    fn get_rows(&self) -> Vec<Vec<Tile>> {
+        let mut rows = self
+            .tiles
+            .chunks(self.width)
+            .map(|row| row.to_vec())
+            .collect::<Vec<Vec<Tile>>>();
+        if self.floor.is_some() {
+            rows.push(vec![Tile::Rock; self.width]);
+        }
+        rows
+    }
+
+    pub fn render_ascii(&self) -> String {
+        let source = SOURCE.0 - self.min_x;
+        self.get_rows()
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, tile)| match tile {
+                        Tile::Air if (x, y) == (source, SOURCE.1) => '+',
+                        Tile::Air => '.',
+                        Tile::Rock => '#',
+                        Tile::Sand => 'o',
+                    })
+                    .chain(std::iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    // Binary PPM image with one pixel per tile.
+    pub fn render_ppm(&self) -> Vec<u8> {
+        let rows = self.get_rows();
+        let mut image = format!("P6\n{} {}\n255\n", self.width, rows.len()).into_bytes();
+        for tile in rows.iter().flatten() {
+            image.extend_from_slice(match tile {
+                Tile::Air => &[20, 20, 30],
+                Tile::Rock => &[110, 110, 110],
+                Tile::Sand => &[230, 190, 90],
+            });
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+fn example() -> Vec<String> {
+    vec![
+        String::from("498,4 -> 498,6 -> 496,6"),
+        String::from("503,4 -> 502,4 -> 502,9 -> 494,9"),
+    ]
+}
+
+#[test]
+fn example_sand() {
+    let mut cave = Cave::parse(&example(), false);
+    assert_eq!(cave.drop_grains(5), 5);
+    assert!(cave
+        .render_ascii()
+        .ends_with(".......o.#..\n.....oooo#..\n.#########..\n"));
+    assert_eq!(cave.fill(), 24);
+    let mut cave = Cave::parse(&example(), true);
+    assert_eq!(cave.fill(), 93);
+    let image = cave.render_ppm();
+    assert!(image.starts_with(b"P6\n23 12\n255\n"));
+    assert_eq!(image.len(), 13 + 23 * 12 * 3);
+
+    // A floor deeper than the source is far from x = 0.
+    let deep = vec![String::from("500,600 -> 501,600")];
+    let mut cave = Cave::parse(&deep, true);
+    assert_eq!(cave.min_x, 0);
+    // Sand eventually spills past x = 0 instead of underflowing.
+    assert!(cave.fill() > 0);
+    assert!(cave.tiles[cave.index(0, cave.height - 1).unwrap()] == Tile::Sand);
+}
@@ -0,0 +1,330 @@
+#[cfg(test)]
+use crate::utils::random::XorShift;
+
+#[derive(Debug, Hash, Eq, PartialEq, Copy, Clone)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub fn distance(&self, other: &Point) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn tuning_frequency(&self) -> isize {
+        self.x * 4_000_000 + self.y
+    }
+}
+
+#[derive(Debug)]
+pub struct Sensor {
+    pub position: Point,
+    pub beacon: Point,
+    pub radius: isize,
+}
+
+impl Sensor {
+    pub fn parse(line: &str) -> Sensor {
+        let numbers = line
+            .split('=')
+            .skip(1)
+            .map(|s| {
+                s.split([',', ':'])
+                    .next()
+                    .unwrap()
+                    .parse::<isize>()
+                    .unwrap()
+            })
+            .collect::<Vec<isize>>();
+        let position = Point {
+            x: numbers[0],
+            y: numbers[1],
+        };
+        let beacon = Point {
+            x: numbers[2],
+            y: numbers[3],
+        };
+        Sensor {
+            position,
+            beacon,
+            radius: position.distance(&beacon),
+        }
+    }
+
+    pub fn covers(&self, point: &Point) -> bool {
+        self.position.distance(point) <= self.radius
+    }
+
+    // The lines `x + y = c` and `x - y = c` running just outside the sensor's range.
+    fn get_boundaries(&self) -> ([isize; 2], [isize; 2]) {
+        let (x, y, r) = (self.position.x, self.position.y, self.radius + 1);
+        ([x + y - r, x + y + r], [x - y - r, x - y + r])
+    }
+}
+
+pub fn get_sensors(lines: &[String]) -> Vec<Sensor> {
+    lines.iter().map(|l| Sensor::parse(l)).collect()
+}
+
+fn merge_ranges(mut ranges: Vec<(isize, isize)>) -> Vec<(isize, isize)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(isize, isize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+// Merged, inclusive ranges of x covered by any sensor on the given row.
+pub fn get_row_coverage(sensors: &[Sensor], y: isize) -> Vec<(isize, isize)> {
+    merge_ranges(
+        sensors
+            .iter()
+            .filter_map(|s| {
+                let reach = s.radius - (s.position.y - y).abs();
+                (reach >= 0).then_some((s.position.x - reach, s.position.x + reach))
+            })
+            .collect(),
+    )
+}
+
+pub fn count_no_beacons(sensors: &[Sensor], y: isize) -> usize {
+    let coverage = get_row_coverage(sensors, y);
+    let mut beacons = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|b| b.y == y)
+        .filter(|b| {
+            coverage
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&b.x))
+        })
+        .collect::<Vec<Point>>();
+    beacons.sort_unstable_by_key(|b| b.x);
+    beacons.dedup();
+    let covered = coverage
+        .iter()
+        .map(|(start, end)| (end - start + 1) as usize)
+        .sum::<usize>();
+    covered - beacons.len()
+}
+
+fn get_intersections(a_lines: &[isize], b_lines: &[isize]) -> Vec<Point> {
+    a_lines
+        .iter()
+        .flat_map(|a| b_lines.iter().map(move |b| (a, b)))
+        .filter(|(a, b)| (*a - *b) % 2 == 0)
+        .map(|(a, b)| Point {
+            x: (a + b) / 2,
+            y: (a - b) / 2,
+        })
+        .collect()
+}
+
+// Lines where the lower boundary of one sensor meets the upper boundary of another.
+fn get_shared_lines(lines: &[[isize; 2]]) -> Vec<isize> {
+    let mut shared = Vec::new();
+    for (i, first) in lines.iter().enumerate() {
+        for second in lines[i + 1..].iter() {
+            if first[0] == second[1] {
+                shared.push(first[0]);
+            }
+            if first[1] == second[0] {
+                shared.push(first[1]);
+            }
+        }
+    }
+    shared
+}
+
+// First x in `start..=end` outside all of the merged ranges.
+fn find_gap(coverage: &[(isize, isize)], start: isize, end: isize) -> Option<isize> {
+    let mut x = start;
+    for (from, to) in coverage {
+        if *from > x {
+            break;
+        }
+        x = x.max(to + 1);
+    }
+    (x <= end).then_some(x)
+}
+
+// First uncovered point inside the search area on the line `x + y = c`, or
+// `x - y = c` if `sum` is false. Each sensor covers one run of x along it.
+fn get_line_gap(sensors: &[Sensor], c: isize, sum: bool, bound: isize) -> Option<Point> {
+    let (start, end) = if sum {
+        ((c - bound).max(0), c.min(bound))
+    } else {
+        (c.max(0), (c + bound).min(bound))
+    };
+    if start > end {
+        return None;
+    }
+    let ranges = sensors
+        .iter()
+        .filter_map(|s| {
+            let (x, y) = (s.position.x, s.position.y);
+            // Offset of the line from the sensor, measured along x.
+            let d = if sum { c - x - y } else { c + y - x };
+            let spare = s.radius - d.abs();
+            (spare >= 0).then_some((x + d.min(0) - spare / 2, x + d.max(0) + spare / 2))
+        })
+        .collect();
+    let x = find_gap(&merge_ranges(ranges), start, end)?;
+    Some(Point {
+        x,
+        y: if sum { c - x } else { x - c },
+    })
+}
+
+// A lone free cell has a covered neighbour, so it is just outside that
+// neighbour's sensor and lies on one of its boundary lines. Walking every
+// boundary line therefore finds it, even when no two boundaries cross there.
+fn get_candidates(sensors: &[Sensor], bound: isize) -> Vec<Point> {
+    let (a_lines, b_lines): (Vec<[isize; 2]>, Vec<[isize; 2]>) =
+        sensors.iter().map(|s| s.get_boundaries()).unzip();
+    let a_points = a_lines
+        .concat()
+        .into_iter()
+        .filter_map(|c| get_line_gap(sensors, c, true, bound));
+    let b_points = b_lines
+        .concat()
+        .into_iter()
+        .filter_map(|c| get_line_gap(sensors, c, false, bound));
+    a_points.chain(b_points).collect()
+}
+
+// The uncovered point usually lies between two sensors whose ranges are
+// exactly one apart, so it sits where two such shared boundary lines cross.
+// Failing that, every boundary line is walked for a gap in its coverage.
+pub fn find_beacon(sensors: &[Sensor], bound: isize) -> Option<Point> {
+    let (a_lines, b_lines): (Vec<[isize; 2]>, Vec<[isize; 2]>) =
+        sensors.iter().map(|s| s.get_boundaries()).unzip();
+    let a_shared = get_shared_lines(&a_lines);
+    let b_shared = get_shared_lines(&b_lines);
+
+    let is_free = |p: &Point| {
+        (0..=bound).contains(&p.x)
+            && (0..=bound).contains(&p.y)
+            && !sensors.iter().any(|s| s.covers(p))
+    };
+    get_intersections(&a_shared, &b_shared)
+        .into_iter()
+        .find(is_free)
+        .or_else(|| get_candidates(sensors, bound).into_iter().next())
+}
+
+#[test]
+fn example() {
+    let lines = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3"
+        .lines()
+        .map(String::from)
+        .collect::<Vec<String>>();
+    let sensors = get_sensors(&lines);
+    assert_eq!(count_no_beacons(&sensors, 10), 26);
+    let beacon = find_beacon(&sensors, 20).unwrap();
+    assert_eq!(beacon, Point { x: 14, y: 11 });
+    assert_eq!(beacon.tuning_frequency(), 56000011);
+}
+
+#[cfg(test)]
+fn get_random_sensors(rng: &mut XorShift, count: usize) -> Vec<Sensor> {
+    (0..count)
+        .map(|_| {
+            let position = Point {
+                x: rng.between(-2, 14) as isize,
+                y: rng.between(-2, 14) as isize,
+            };
+            let beacon = Point {
+                x: position.x + rng.between(-6, 6) as isize,
+                y: position.y + rng.between(-6, 6) as isize,
+            };
+            Sensor {
+                position,
+                beacon,
+                radius: position.distance(&beacon),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn matches_brute_force() {
+    let mut rng = XorShift::new(15);
+    let bound = 12;
+    let mut checked = 0;
+    let mut on_edge = 0;
+    while checked < 2000 {
+        let count = rng.between(3, 8) as usize;
+        let sensors = get_random_sensors(&mut rng, count);
+        let free = (0..=bound)
+            .flat_map(|y| (0..=bound).map(move |x| Point { x, y }))
+            .filter(|p| !sensors.iter().any(|s| s.covers(p)))
+            .collect::<Vec<Point>>();
+        if free.len() == 1 {
+            assert_eq!(find_beacon(&sensors, bound), Some(free[0]));
+            assert!(get_candidates(&sensors, bound).contains(&free[0]));
+            if [free[0].x, free[0].y]
+                .iter()
+                .any(|v| *v == 0 || *v == bound)
+            {
+                on_edge += 1;
+            }
+            checked += 1;
+        }
+    }
+    assert!(on_edge > 0);
+
+    // Free cell on the top edge, away from the corners.
+    let sensors = [
+        ((1, 0), 2),
+        ((7, 0), 2),
+        ((4, 4), 3),
+        ((0, 6), 2),
+        ((8, 6), 2),
+    ]
+    .map(|((x, y), radius)| Sensor {
+        position: Point { x, y },
+        beacon: Point { x: x + radius, y },
+        radius,
+    });
+    let beacon = Point { x: 4, y: 0 };
+    assert!(!sensors.iter().any(|s| s.covers(&beacon)));
+    assert!(get_candidates(&sensors, 8).contains(&beacon));
+    assert_eq!(find_beacon(&sensors, 8), Some(beacon));
+
+    // Free cell inside the area where every nearby boundary runs along the
+    // same diagonal, so no two boundary lines cross at it.
+    let sensors = [
+        ((4, 7), 7),
+        ((3, 12), 7),
+        ((4, 10), 10),
+        ((4, 14), 8),
+        ((12, 11), 6),
+        ((13, 0), 7),
+        ((4, -1), 10),
+    ]
+    .map(|((x, y), radius)| Sensor {
+        position: Point { x, y },
+        beacon: Point { x: x + radius, y },
+        radius,
+    });
+    assert_eq!(find_beacon(&sensors, 12), Some(Point { x: 10, y: 5 }));
+}
@@ -0,0 +1,497 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+// The DP keeps a table of 2^n masks per time limit and combining agents costs
+// O(3^n), so more valves with flow than this is impractical.
+const MAX_USEFUL_VALVES: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct Valve {
+    pub name: String,
+    pub flow_rate: u32,
+    pub tunnels: Vec<usize>,
+}
+
+pub struct Network {
+    pub valves: Vec<Valve>,
+    pub distances: Vec<Vec<u32>>,
+    // First tunnel to take on a shortest route from `i` to `j`.
+    pub next: Vec<Vec<usize>>,
+    // Valves worth opening; bit `i` of a mask stands for `useful[i]`.
+    pub useful: Vec<usize>,
+    pub start: usize,
+}
+
+impl Network {
+    pub fn parse(lines: &[String]) -> Network {
+        let parsed = lines
+            .iter()
+            .map(|line| {
+                let (valve, tunnels) = line.split_once("; ").unwrap();
+                let parts = valve.split(' ').collect::<Vec<&str>>();
+                let flow_rate = parts[4].trim_start_matches("rate=").parse().unwrap();
+                let tunnels = tunnels
+                    .split(' ')
+                    .skip(4)
+                    .map(|s| s.trim_end_matches(','))
+                    .collect::<Vec<&str>>();
+                (parts[1], flow_rate, tunnels)
+            })
+            .collect::<Vec<(&str, u32, Vec<&str>)>>();
+        let indices = parsed
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _, _))| (*name, i))
+            .collect::<HashMap<&str, usize>>();
+        let valves = parsed
+            .iter()
+            .map(|(name, flow_rate, tunnels)| Valve {
+                name: name.to_string(),
+                flow_rate: *flow_rate,
+                tunnels: tunnels
+                    .iter()
+                    .map(|t| {
+                        *indices
+                            .get(t)
+                            .unwrap_or_else(|| panic!("Unknown valve {}", t))
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<Valve>>();
+        let useful = (0..valves.len())
+            .filter(|i| valves[*i].flow_rate > 0)
+            .collect::<Vec<usize>>();
+        assert!(
+            useful.len() <= MAX_USEFUL_VALVES,
+            "Too many valves with flow: {} (at most {})",
+            useful.len(),
+            MAX_USEFUL_VALVES
+        );
+        let (distances, next) = get_distances(&valves);
+        Network {
+            distances,
+            next,
+            start: *indices.get("AA").expect("Missing valve AA"),
+            valves,
+            useful,
+        }
+    }
+
+    // Dynamic programming over (position, opened mask, elapsed minutes). Keeps
+    // the best pressure released by one agent opening only valves within each
+    // mask, and with `trace` enough of the search to recover how.
+    fn get_plan(&self, minutes: u32, trace: bool) -> Plan {
+        let start = (0, self.start, 0);
+        let mut plan = Plan {
+            best: vec![0; 1 << self.useful.len()],
+            ends: vec![start; 1 << self.useful.len()],
+            parents: HashMap::new(),
+        };
+        let mut layers: Vec<Layer> = vec![HashMap::new(); minutes as usize + 1];
+        layers[0].insert((self.start, 0), (0, None));
+        for elapsed in 0..=minutes {
+            for ((position, mask), (pressure, parent)) in
+                std::mem::take(&mut layers[elapsed as usize])
+            {
+                let state = (elapsed, position, mask);
+                if let (true, Some(parent)) = (trace, parent) {
+                    plan.parents.insert(state, parent);
+                }
+                if pressure > plan.best[mask] {
+                    plan.best[mask] = pressure;
+                    plan.ends[mask] = state;
+                }
+                for (bit, valve) in self.useful.iter().enumerate() {
+                    if mask & (1 << bit) != 0 {
+                        continue;
+                    }
+                    let opened = elapsed + self.distances[position][*valve] + 1;
+                    if opened >= minutes {
+                        continue;
+                    }
+                    let released = pressure + self.valves[*valve].flow_rate * (minutes - opened);
+                    let entry = layers[opened as usize]
+                        .entry((*valve, mask | (1 << bit)))
+                        .or_insert((0, None));
+                    if released > entry.0 {
+                        *entry = (released, Some(state));
+                    }
+                }
+            }
+        }
+        for bit in 0..self.useful.len() {
+            for mask in 0..plan.best.len() {
+                if mask & (1 << bit) != 0 && plan.best[mask ^ (1 << bit)] > plan.best[mask] {
+                    plan.best[mask] = plan.best[mask ^ (1 << bit)];
+                    plan.ends[mask] = plan.ends[mask ^ (1 << bit)];
+                }
+            }
+        }
+        plan
+    }
+
+    pub fn get_best_pressures(&self, minutes: u32) -> Vec<u32> {
+        self.get_plan(minutes, false).best
+    }
+
+    // Best total pressure for agents working in parallel, each with its own
+    // time limit and never opening the same valve.
+    pub fn get_max_pressure(&self, minutes: &[u32]) -> u32 {
+        let Some((first, rest)) = minutes.split_first() else {
+            return 0;
+        };
+        let mut tables: HashMap<u32, Vec<u32>> = HashMap::new();
+        for m in minutes {
+            tables
+                .entry(*m)
+                .or_insert_with(|| self.get_best_pressures(*m));
+        }
+        let mut combined = tables[first].clone();
+        for m in rest {
+            combined = combine(&combined, &tables[m]).0;
+        }
+        combined[combined.len() - 1]
+    }
+
+    pub fn get_schedule(&self, minutes: &[u32]) -> Schedule {
+        let Some((first, rest)) = minutes.split_first() else {
+            return Schedule { agents: Vec::new() };
+        };
+        let mut plans: HashMap<u32, Plan> = HashMap::new();
+        for m in minutes {
+            plans.entry(*m).or_insert_with(|| self.get_plan(*m, true));
+        }
+        let mut combined = plans[first].best.clone();
+        let mut splits = Vec::new();
+        for m in rest {
+            let (next, split) = combine(&combined, &plans[m].best);
+            combined = next;
+            splits.push(split);
+        }
+
+        let mut masks = vec![0; minutes.len()];
+        let mut mask = combined.len() - 1;
+        for i in (1..minutes.len()).rev() {
+            let kept = splits[i - 1][mask];
+            masks[i] = mask ^ kept;
+            mask = kept;
+        }
+        masks[0] = mask;
+
+        let mut schedule = Schedule {
+            agents: minutes
+                .iter()
+                .zip(masks)
+                .map(|(m, mask)| self.get_agent_schedule(&plans[m], *m, mask))
+                .collect(),
+        };
+        let mut opened = HashSet::new();
+        for agent in schedule.agents.iter_mut() {
+            let (released, pressure) = self.replay(agent, &mut opened).unwrap();
+            for (event, released) in agent.events.iter_mut().zip(released) {
+                event.released = released;
+            }
+            agent.pressure = pressure;
+        }
+        schedule
+    }
+
+    fn get_agent_schedule(&self, plan: &Plan, minutes: u32, mask: usize) -> AgentSchedule {
+        let mut opens = Vec::new();
+        let mut state = plan.ends[mask];
+        while let Some(parent) = plan.parents.get(&state) {
+            opens.push(state.1);
+            state = *parent;
+        }
+        opens.reverse();
+
+        let mut events = Vec::new();
+        let mut position = self.start;
+        let mut minute = 0;
+        for valve in opens {
+            while position != valve {
+                position = self.next[position][valve];
+                minute += 1;
+                events.push(Event {
+                    minute,
+                    action: Action::Move(self.valves[position].name.clone()),
+                    released: 0,
+                });
+            }
+            minute += 1;
+            events.push(Event {
+                minute,
+                action: Action::Open(self.valves[valve].name.clone()),
+                released: 0,
+            });
+        }
+        AgentSchedule {
+            minutes,
+            events,
+            pressure: 0,
+        }
+    }
+
+    fn get_valve(&self, name: &str) -> Result<usize, ScheduleError> {
+        self.valves
+            .iter()
+            .position(|v| v.name == name)
+            .ok_or_else(|| ScheduleError::UnknownValve(name.to_string()))
+    }
+
+    // Walks one agent's events, returning the pressure released by the end of
+    // each event's minute and by the deadline.
+    fn replay(
+        &self,
+        agent: &AgentSchedule,
+        opened: &mut HashSet<usize>,
+    ) -> Result<(Vec<u32>, u32), ScheduleError> {
+        let mut position = self.start;
+        let mut minute = 0;
+        let mut rate = 0;
+        let mut released = 0;
+        let mut totals = Vec::new();
+        for event in agent.events.iter() {
+            if event.minute <= minute {
+                return Err(ScheduleError::OutOfOrder(event.minute));
+            }
+            if event.minute > agent.minutes {
+                return Err(ScheduleError::OutOfTime(event.minute));
+            }
+            released += rate * (event.minute - minute);
+            minute = event.minute;
+            match &event.action {
+                Action::Move(name) => {
+                    let valve = self.get_valve(name)?;
+                    if !self.valves[position].tunnels.contains(&valve) {
+                        return Err(ScheduleError::NoTunnel(minute, name.clone()));
+                    }
+                    position = valve;
+                }
+                Action::Open(name) => {
+                    let valve = self.get_valve(name)?;
+                    if valve != position {
+                        return Err(ScheduleError::NotAtValve(minute, name.clone()));
+                    }
+                    if !opened.insert(valve) {
+                        return Err(ScheduleError::AlreadyOpen(minute, name.clone()));
+                    }
+                    rate += self.valves[valve].flow_rate;
+                }
+            }
+            totals.push(released);
+        }
+        released += rate * (agent.minutes - minute);
+        Ok((totals, released))
+    }
+
+    // Checks that every move follows a tunnel, every valve is opened once from
+    // where the agent stands, and the recorded pressures are right. Returns the
+    // total pressure released.
+    pub fn validate(&self, schedule: &Schedule) -> Result<u32, ScheduleError> {
+        let mut opened = HashSet::new();
+        for agent in schedule.agents.iter() {
+            let (released, pressure) = self.replay(agent, &mut opened)?;
+            for (event, released) in agent.events.iter().zip(released) {
+                if event.released != released {
+                    return Err(ScheduleError::WrongPressure(event.minute, released));
+                }
+            }
+            if agent.pressure != pressure {
+                return Err(ScheduleError::WrongPressure(agent.minutes, pressure));
+            }
+        }
+        Ok(schedule.get_pressure())
+    }
+}
+
+// (elapsed minutes, position, opened mask)
+type State = (u32, usize, usize);
+type Layer = HashMap<(usize, usize), (u32, Option<State>)>;
+
+struct Plan {
+    best: Vec<u32>,
+    ends: Vec<State>,
+    parents: HashMap<State, State>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Move(String),
+    Open(String),
+}
+
+// `released` is the pressure this agent has released by the end of `minute`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub minute: u32,
+    pub action: Action,
+    pub released: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentSchedule {
+    pub minutes: u32,
+    pub events: Vec<Event>,
+    pub pressure: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub agents: Vec<AgentSchedule>,
+}
+
+impl Schedule {
+    pub fn get_pressure(&self) -> u32 {
+        self.agents.iter().map(|a| a.pressure).sum()
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, agent) in self.agents.iter().enumerate() {
+            writeln!(f, "Agent {} ({} minutes):", i + 1, agent.minutes)?;
+            for event in agent.events.iter() {
+                let action = match &event.action {
+                    Action::Move(valve) => format!("move to {}", valve),
+                    Action::Open(valve) => format!("open {}", valve),
+                };
+                writeln!(
+                    f,
+                    "  minute {:>2}: {:<12} released {}",
+                    event.minute, action, event.released
+                )?;
+            }
+            writeln!(f, "  total released {}", agent.pressure)?;
+        }
+        write!(f, "Total released {}", self.get_pressure())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    UnknownValve(String),
+    OutOfOrder(u32),
+    OutOfTime(u32),
+    NoTunnel(u32, String),
+    NotAtValve(u32, String),
+    AlreadyOpen(u32, String),
+    WrongPressure(u32, u32),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleError::UnknownValve(name) => write!(f, "unknown valve {}", name),
+            ScheduleError::OutOfOrder(minute) => write!(f, "minute {}: out of order", minute),
+            ScheduleError::OutOfTime(minute) => write!(f, "minute {}: past the deadline", minute),
+            ScheduleError::NoTunnel(minute, name) => {
+                write!(f, "minute {}: no tunnel to {}", minute, name)
+            }
+            ScheduleError::NotAtValve(minute, name) => {
+                write!(f, "minute {}: not standing at {}", minute, name)
+            }
+            ScheduleError::AlreadyOpen(minute, name) => {
+                write!(f, "minute {}: {} is already open", minute, name)
+            }
+            ScheduleError::WrongPressure(minute, expected) => {
+                write!(f, "minute {}: released should be {}", minute, expected)
+            }
+        }
+    }
+}
+
+// All-pairs tunnel distances and next hops with Floyd–Warshall.
+fn get_distances(valves: &[Valve]) -> (Vec<Vec<u32>>, Vec<Vec<usize>>) {
+    let n = valves.len();
+    let mut distances = vec![vec![u32::MAX / 2; n]; n];
+    let mut next = vec![vec![0; n]; n];
+    for (i, valve) in valves.iter().enumerate() {
+        distances[i][i] = 0;
+        next[i][i] = i;
+        for j in valve.tunnels.iter() {
+            distances[i][*j] = 1;
+            next[i][*j] = *j;
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                let through = distances[i][k] + distances[k][j];
+                if through < distances[i][j] {
+                    distances[i][j] = through;
+                    next[i][j] = next[i][k];
+                }
+            }
+        }
+    }
+    (distances, next)
+}
+
+// Splits every mask between two submask-monotone tables in the best way,
+// returning the best totals and the part of each mask given to `a`.
+pub fn combine(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<usize>) {
+    (0..a.len())
+        .map(|mask| {
+            let mut best = (a[0] + b[mask], 0);
+            let mut subset = mask;
+            while subset > 0 {
+                best = best.max((a[subset] + b[mask ^ subset], subset));
+                subset = (subset - 1) & mask;
+            }
+            best
+        })
+        .unzip()
+}
+
+#[cfg(test)]
+fn example() -> Network {
+    let lines = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II"
+        .lines()
+        .map(String::from)
+        .collect::<Vec<String>>();
+    Network::parse(&lines)
+}
+
+#[test]
+fn example_pressure() {
+    let network = example();
+    assert_eq!(network.distances[network.start][7], 5);
+    assert_eq!(network.get_max_pressure(&[30]), 1651);
+    assert_eq!(network.get_max_pressure(&[26, 26]), 1707);
+    assert!(network.get_max_pressure(&[26, 26, 26]) >= 1707);
+    assert_eq!(network.get_max_pressure(&[]), 0);
+    assert!(network.get_schedule(&[]).agents.is_empty());
+}
+
+#[test]
+fn example_schedule() {
+    let network = example();
+    let schedule = network.get_schedule(&[26, 26]);
+    assert_eq!(schedule.get_pressure(), 1707);
+    assert_eq!(network.validate(&schedule), Ok(1707));
+    assert!(schedule.agents.iter().all(|a| a.events.len() <= 26));
+    assert!(schedule.to_string().ends_with("Total released 1707"));
+
+    let mut broken = schedule.clone();
+    broken.agents[1].events = schedule.agents[0].events.clone();
+    assert!(matches!(
+        network.validate(&broken),
+        Err(ScheduleError::AlreadyOpen(_, _))
+    ));
+    let mut broken = schedule.clone();
+    broken.agents[0].events[0].action = Action::Move(String::from("HH"));
+    assert_eq!(
+        network.validate(&broken),
+        Err(ScheduleError::NoTunnel(1, String::from("HH")))
+    );
+}
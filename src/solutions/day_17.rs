@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+const WIDTH: usize = 7;
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+// Pruning runs once this many rows are stored.
+const PRUNE_THRESHOLD: usize = 256;
+
+// Each piece as row masks from the bottom up, already shifted to start two
+// units from the left wall. Bit `WIDTH - 1 - x` is column `x`.
+const PIECES: [(usize, [u8; 4]); 5] = [
+    (1, [0b0011110, 0, 0, 0]),
+    (3, [0b0001000, 0b0011100, 0b0001000, 0]),
+    (3, [0b0011100, 0b0000100, 0b0000100, 0]),
+    (4, [0b0010000, 0b0010000, 0b0010000, 0b0010000]),
+    (2, [0b0011000, 0b0011000, 0, 0]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    Left,
+    Right,
+}
+
+pub fn get_movements(lines: &[String]) -> Vec<Move> {
+    assert_eq!(lines.len(), 1);
+    lines[0]
+        .chars()
+        .map(|c| match c {
+            '<' => Move::Left,
+            '>' => Move::Right,
+            _ => panic!("Unknown move: {}", c),
+        })
+        .collect()
+}
+
+pub struct Chamber<'a> {
+    moves: &'a [Move],
+    // Row masks starting at `pruned`; rows below can no longer be reached.
+    rows: Vec<u8>,
+    pruned: usize,
+    pub height: usize,
+    pub rocks: usize,
+    pub jet: usize,
+}
+
+impl<'a> Chamber<'a> {
+    pub fn new(moves: &'a [Move]) -> Chamber<'a> {
+        Chamber {
+            moves,
+            rows: Vec::new(),
+            pruned: 0,
+            height: 0,
+            rocks: 0,
+            jet: 0,
+        }
+    }
+
+    fn get_row(&self, y: usize) -> u8 {
+        self.rows.get(y - self.pruned).copied().unwrap_or(0)
+    }
+
+    fn is_colliding(&self, shape: &[u8], y: usize) -> bool {
+        shape
+            .iter()
+            .enumerate()
+            .any(|(i, row)| self.get_row(y + i) & row != 0)
+    }
+
+    pub fn drop_rock(&mut self) {
+        let (height, mut shape) = PIECES[self.rocks % PIECES.len()];
+        let shape = &mut shape[..height];
+        let mut y = self.height + 3;
+        loop {
+            let m = self.moves[self.jet];
+            self.jet = (self.jet + 1) % self.moves.len();
+            let mut shifted = [0; 4];
+            let shifted = &mut shifted[..height];
+            let blocked = match m {
+                Move::Left => shape.iter().any(|row| row & LEFT_WALL != 0),
+                Move::Right => shape.iter().any(|row| row & RIGHT_WALL != 0),
+            };
+            if !blocked {
+                for (new, row) in shifted.iter_mut().zip(shape.iter()) {
+                    *new = match m {
+                        Move::Left => row << 1,
+                        Move::Right => row >> 1,
+                    };
+                }
+                if !self.is_colliding(shifted, y) {
+                    shape.copy_from_slice(shifted);
+                }
+            }
+            if y == self.pruned || self.is_colliding(shape, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        self.height = self.height.max(y + height);
+        self.rows.resize(self.height - self.pruned, 0);
+        for (i, row) in shape.iter().enumerate() {
+            self.rows[y + i - self.pruned] |= row;
+        }
+        self.rocks += 1;
+        if self.rows.len() >= PRUNE_THRESHOLD {
+            self.prune();
+        }
+    }
+
+    pub fn drop_rocks(&mut self, count: usize) {
+        for _ in 0..count {
+            self.drop_rock();
+        }
+    }
+
+    // Flood fills the open cells reachable from above by moving left, right or
+    // down, and returns the index of the row supporting the lowest of them.
+    // Nothing below it can affect later rocks.
+    fn get_floor(&self) -> usize {
+        let top = self.rows.len();
+        let mut reachable = (1u8 << WIDTH) - 1;
+        let mut lowest = top;
+        for i in (0..top).rev() {
+            let open = !self.rows[i] & ((1 << WIDTH) - 1);
+            let mut cells = reachable & open;
+            loop {
+                let spread = (cells | (cells << 1) | (cells >> 1)) & open;
+                if spread == cells {
+                    break;
+                }
+                cells = spread;
+            }
+            if cells == 0 {
+                break;
+            }
+            reachable = cells;
+            lowest = i;
+        }
+        lowest.saturating_sub(1)
+    }
+
+    fn prune(&mut self) {
+        let keep_from = self.get_floor();
+        self.rows.drain(..keep_from);
+        self.pruned += keep_from;
+    }
+
+    // The rows that can still affect later rocks, from the supporting floor
+    // up. Two chambers with the same surface behave identically from here.
+    pub fn get_surface(&self) -> Vec<u8> {
+        self.rows[self.get_floor()..].to_vec()
+    }
+
+    pub fn get_stored_rows(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+    pub growth: usize,
+}
+
+pub struct Tower {
+    // Height after each number of rocks, up to the first repeat of the cycle.
+    pub heights: Vec<usize>,
+    pub cycle: Cycle,
+}
+
+impl Tower {
+    // Drops rocks until the (piece, jet, surface) state repeats.
+    pub fn simulate(moves: &[Move]) -> Tower {
+        let mut chamber = Chamber::new(moves);
+        let mut heights = vec![0];
+        let mut seen: HashMap<(usize, usize, Vec<u8>), usize> = HashMap::new();
+        loop {
+            chamber.drop_rock();
+            heights.push(chamber.height);
+            let key = (
+                chamber.rocks % PIECES.len(),
+                chamber.jet,
+                chamber.get_surface(),
+            );
+            if let Some(start) = seen.insert(key, chamber.rocks) {
+                return Tower {
+                    cycle: Cycle {
+                        start,
+                        length: chamber.rocks - start,
+                        growth: chamber.height - heights[start],
+                    },
+                    heights,
+                };
+            }
+        }
+    }
+
+    pub fn get_height(&self, rocks: usize) -> usize {
+        if rocks < self.heights.len() {
+            return self.heights[rocks];
+        }
+        let cycles = (rocks - self.cycle.start) / self.cycle.length;
+        let remaining = (rocks - self.cycle.start) % self.cycle.length;
+        self.heights[self.cycle.start + remaining] + cycles * self.cycle.growth
+    }
+}
+
+#[test]
+fn example() {
+    let moves = get_movements(&[String::from(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>")]);
+    let mut chamber = Chamber::new(&moves);
+    for _ in 0..2022 {
+        chamber.drop_rock();
+    }
+    assert_eq!(chamber.height, 3068);
+    let tower = Tower::simulate(&moves);
+    assert_eq!(tower.cycle.length, 35);
+    assert_eq!(tower.get_height(2022), 3068);
+    assert_eq!(tower.get_height(1_000_000_000_000), 1514285714288);
+}
+
+#[test]
+fn direct_simulation() {
+    let moves = get_movements(&[String::from(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>")]);
+    let tower = Tower::simulate(&moves);
+    let mut chamber = Chamber::new(&moves);
+    chamber.drop_rocks(200_000);
+    assert_eq!(chamber.height, tower.get_height(200_000));
+    assert!(chamber.get_stored_rows() <= PRUNE_THRESHOLD);
+}
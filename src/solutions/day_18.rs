@@ -0,0 +1,54 @@
+use crate::utils::voxel::{Voxel, VoxelSet};
+
+pub fn get_cubes(lines: &[String]) -> VoxelSet {
+    let voxels = lines
+        .iter()
+        .map(|line| {
+            let mut parts = line.split(',').map(|p| p.parse::<i32>().unwrap());
+            Voxel::new(
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+            )
+        })
+        .collect::<Vec<Voxel>>();
+    VoxelSet::from_voxels(&voxels)
+}
+
+#[cfg(test)]
+fn example() -> VoxelSet {
+    let lines = "2,2,2 1,2,2 3,2,2 2,1,2 2,3,2 2,2,1 2,2,3 2,2,4 2,2,6 1,2,5 3,2,5 2,1,5 2,3,5"
+        .split(' ')
+        .map(String::from)
+        .collect::<Vec<String>>();
+    get_cubes(&lines)
+}
+
+#[test]
+fn example_surface() {
+    let cubes = example();
+    assert_eq!(cubes.get_surface_area(), 64);
+    assert_eq!(cubes.get_exterior_surface_area(), 58);
+    let pockets = cubes.get_air_pockets();
+    assert_eq!(
+        pockets.iter().map(|p| p.len()).collect::<Vec<usize>>(),
+        vec![1]
+    );
+}
+
+#[test]
+fn example_mesh() {
+    use crate::utils::mesh::{Faces, Mesh};
+
+    let cubes = example();
+    let exposed = Mesh::from_voxels(&cubes, Faces::Exposed, false);
+    assert_eq!(exposed.triangles.len(), 2 * 64);
+    let exterior = Mesh::from_voxels(&cubes, Faces::Exterior, false);
+    assert_eq!(exterior.triangles.len(), 2 * 58);
+    assert!(
+        Mesh::from_voxels(&cubes, Faces::Exterior, true)
+            .triangles
+            .len()
+            < 2 * 58
+    );
+}
@@ -0,0 +1,419 @@
+#[derive(Debug, Clone)]
+pub struct Blueprint {
+    pub id: u32,
+    pub resources: Vec<String>,
+    // `costs[robot][resource]`, with robots indexed by the resource they
+    // collect. Resources without a recipe cannot be mined.
+    pub costs: Vec<Option<Vec<u32>>>,
+    pub start: Vec<u32>,
+    pub objective: usize,
+    // Building more of a robot than the most any recipe spends per minute
+    // cannot help, since only one robot is built each minute.
+    pub max_robots: Vec<u32>,
+}
+
+fn get_amounts(text: &str) -> Vec<(u32, &str)> {
+    text.split([',', '{', '}'])
+        .flat_map(|part| part.split(" and "))
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (a, b) = part
+                .split_once(|c: char| c == ':' || c.is_whitespace())
+                .unwrap_or_else(|| panic!("Invalid amount: {}", part));
+            let (a, b) = (a.trim(), b.trim());
+            match a.parse::<u32>() {
+                Ok(amount) => (amount, b),
+                Err(_) => (b.parse().unwrap(), a),
+            }
+        })
+        .collect()
+}
+
+impl Blueprint {
+    // Reads either the puzzle's "Each ore robot costs 4 ore." sentences or
+    // recipe lines like "robot geode costs {ore: 2, obsidian: 7}", separated by
+    // full stops or newlines. An optional "start {ore: 1}" sets the starting
+    // robots, otherwise there is one robot for the first resource.
+    pub fn parse(text: &str, id: u32) -> Blueprint {
+        let mut id = id;
+        let mut text = text.trim();
+        if let Some(rest) = text.strip_prefix("Blueprint ") {
+            let (number, rest) = rest.split_once(':').unwrap();
+            id = number.trim().parse().unwrap();
+            text = rest;
+        }
+        let mut resources: Vec<String> = Vec::new();
+        let mut index = |name: &str| match resources.iter().position(|r| r == name) {
+            Some(i) => i,
+            None => {
+                resources.push(name.to_string());
+                resources.len() - 1
+            }
+        };
+        let mut recipes = Vec::new();
+        let mut start = Vec::new();
+        for statement in text.split(['.', '\n']).map(|s| s.trim()) {
+            if statement.is_empty() {
+                continue;
+            }
+            if let Some(amounts) = statement.strip_prefix("start ") {
+                for (amount, name) in get_amounts(amounts) {
+                    start.push((index(name), amount));
+                }
+                continue;
+            }
+            let (robot, amounts) = statement
+                .split_once(" costs ")
+                .unwrap_or_else(|| panic!("Invalid recipe: {}", statement));
+            let robot = robot
+                .trim_start_matches("Each ")
+                .trim_start_matches("robot ")
+                .trim_end_matches(" robot");
+            let robot = index(robot);
+            let amounts = get_amounts(amounts)
+                .into_iter()
+                .map(|(amount, name)| (index(name), amount))
+                .collect::<Vec<(usize, u32)>>();
+            recipes.push((robot, amounts));
+        }
+
+        let mut costs = vec![None; resources.len()];
+        for (robot, amounts) in recipes {
+            let mut cost = vec![0; resources.len()];
+            for (resource, amount) in amounts {
+                cost[resource] += amount;
+            }
+            costs[robot] = Some(cost);
+        }
+        let mut robots = vec![0; resources.len()];
+        if start.is_empty() {
+            robots[0] = 1;
+        }
+        for (resource, amount) in start {
+            robots[resource] += amount;
+        }
+        let objective = resources
+            .iter()
+            .position(|r| r == "geode")
+            .unwrap_or(resources.len() - 1);
+        let mut blueprint = Blueprint {
+            id,
+            resources,
+            costs,
+            start: robots,
+            objective,
+            max_robots: Vec::new(),
+        };
+        blueprint.set_objective(objective);
+        blueprint
+    }
+
+    pub fn set_objective(&mut self, objective: usize) {
+        self.objective = objective;
+        self.max_robots = (0..self.resources.len())
+            .map(|resource| {
+                if resource == objective {
+                    return u32::MAX;
+                }
+                self.costs
+                    .iter()
+                    .flatten()
+                    .map(|cost| cost[resource])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+    }
+
+    pub fn get_resource(&self, name: &str) -> Option<usize> {
+        self.resources.iter().position(|r| r == name)
+    }
+}
+
+// Blueprints are one per line in the puzzle format, or blocks of recipe lines
+// separated by blank lines.
+pub fn get_blueprints(lines: &[String]) -> Vec<Blueprint> {
+    let mut texts: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        if (line.trim().is_empty() || line.starts_with("Blueprint")) && !current.is_empty() {
+            texts.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        texts.push(current);
+    }
+    texts
+        .iter()
+        .filter(|t| !t.trim().is_empty())
+        .enumerate()
+        .map(|(i, text)| Blueprint::parse(text, i as u32 + 1))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    minutes_left: u32,
+    inventory: Vec<u32>,
+    robots: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub amount: u32,
+    // The minute each robot is finished in and the resource it collects.
+    pub build_order: Vec<(u32, usize)>,
+}
+
+struct Search<'a> {
+    blueprint: &'a Blueprint,
+    minutes: u32,
+    path: Vec<(u32, usize)>,
+    best: Plan,
+}
+
+impl<'a> Search<'a> {
+    // Relaxed bound: every other robot is free each minute, and an objective
+    // robot is built whenever its cost is covered. If building an objective
+    // robot spends the objective itself, that greedy choice can undercount, so
+    // every objective robot is taken to be free instead.
+    fn get_upper_bound(&self, state: &State) -> u32 {
+        let objective = self.blueprint.objective;
+        let (inventory, robots, left) = (
+            state.inventory[objective],
+            state.robots[objective],
+            state.minutes_left,
+        );
+        let cost = match &self.blueprint.costs[objective] {
+            Some(cost) if cost[objective] == 0 => cost,
+            Some(_) => return inventory + robots * left + left * left.saturating_sub(1) / 2,
+            None => return inventory + robots * left,
+        };
+        let mut inventory = state.inventory.clone();
+        let mut robots = state.robots.clone();
+        for _ in 0..state.minutes_left {
+            let build = (0..cost.len()).all(|r| inventory[r] >= cost[r]);
+            for resource in 0..inventory.len() {
+                if build {
+                    inventory[resource] -= cost[resource];
+                }
+                inventory[resource] += robots[resource];
+                if resource != objective && self.blueprint.costs[resource].is_some() {
+                    robots[resource] += 1;
+                }
+            }
+            if build {
+                robots[objective] += 1;
+            }
+        }
+        inventory[objective]
+    }
+
+    // Minutes until a robot can be built and finished, if it ever can.
+    fn get_build_time(&self, state: &State, cost: &[u32]) -> Option<u32> {
+        let mut wait = 0;
+        for (resource, cost) in cost.iter().enumerate() {
+            if *cost <= state.inventory[resource] {
+                continue;
+            }
+            if state.robots[resource] == 0 {
+                return None;
+            }
+            let missing = cost - state.inventory[resource];
+            wait = wait.max(missing.div_ceil(state.robots[resource]));
+        }
+        Some(wait + 1)
+    }
+
+    fn explore(&mut self, state: &State) {
+        let objective = self.blueprint.objective;
+        let idle = state.inventory[objective] + state.robots[objective] * state.minutes_left;
+        if idle > self.best.amount {
+            self.best = Plan {
+                amount: idle,
+                build_order: self.path.clone(),
+            };
+        }
+        if self.get_upper_bound(state) <= self.best.amount {
+            return;
+        }
+        let mut order = (0..self.blueprint.resources.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|robot| *robot != objective);
+        for robot in order {
+            let cost = match &self.blueprint.costs[robot] {
+                Some(cost) => cost,
+                None => continue,
+            };
+            let cap = self.blueprint.max_robots[robot];
+            if robot != objective
+                && state.robots[robot] * state.minutes_left + state.inventory[robot]
+                    >= cap * state.minutes_left
+            {
+                continue;
+            }
+            let time = match self.get_build_time(state, cost) {
+                Some(time) if time < state.minutes_left => time,
+                _ => continue,
+            };
+            let mut next = state.clone();
+            next.minutes_left -= time;
+            for resource in 0..next.inventory.len() {
+                next.inventory[resource] += state.robots[resource] * time;
+                next.inventory[resource] -= cost[resource];
+            }
+            next.robots[robot] += 1;
+            self.path.push((self.minutes - next.minutes_left, robot));
+            self.explore(&next);
+            self.path.pop();
+        }
+    }
+}
+
+// Branch and bound over which robot to build next, skipping straight to the
+// minute it can be afforded. Maximises the blueprint's objective resource.
+pub fn optimize(blueprint: &Blueprint, minutes: u32) -> Plan {
+    let mut search = Search {
+        blueprint,
+        minutes,
+        path: Vec::new(),
+        best: Plan::default(),
+    };
+    search.explore(&State {
+        minutes_left: minutes,
+        inventory: vec![0; blueprint.resources.len()],
+        robots: blueprint.start.clone(),
+    });
+    search.best
+}
+
+// Optimises every blueprint on its own thread.
+pub fn optimize_all(blueprints: &[Blueprint], minutes: u32) -> Vec<Plan> {
+    std::thread::scope(|scope| {
+        let handles = blueprints
+            .iter()
+            .map(|blueprint| scope.spawn(move || optimize(blueprint, minutes)))
+            .collect::<Vec<_>>();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+#[test]
+fn example() {
+    let blueprints = get_blueprints(&[
+        String::from("Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian."),
+        String::from("Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian."),
+    ]);
+    let plans = optimize_all(&blueprints, 24);
+    assert_eq!(
+        plans.iter().map(|p| p.amount).collect::<Vec<u32>>(),
+        vec![9, 12]
+    );
+    let clay = blueprints[0].get_resource("clay").unwrap();
+    assert_eq!(plans[0].build_order[..3], [(3, clay), (5, clay), (7, clay)]);
+    assert_eq!(optimize(&blueprints[0], 32).amount, 56);
+}
+
+#[test]
+fn recipes() {
+    let lines = "robot ore costs {ore: 4}
+robot clay costs {ore: 2}
+robot obsidian costs {ore: 3, clay: 14}
+robot geode costs {ore: 2, obsidian: 7}
+
+start {ore: 1, wood: 1}
+robot wood costs {wood: 3}
+robot chair costs {wood: 4}
+robot table costs {wood: 2, chair: 2}"
+        .lines()
+        .map(String::from)
+        .collect::<Vec<String>>();
+    let mut blueprints = get_blueprints(&lines);
+    assert_eq!(blueprints.len(), 2);
+    assert_eq!(optimize(&blueprints[0], 24).amount, 9);
+
+    let furniture = &mut blueprints[1];
+    assert_eq!(furniture.get_resource("table"), Some(furniture.objective));
+    assert_eq!(furniture.start, vec![1, 1, 0, 0]);
+    let chair = furniture.get_resource("chair").unwrap();
+    furniture.set_objective(chair);
+    let plan = optimize(furniture, 10);
+    assert!(plan.amount > 0);
+}
+
+#[cfg(test)]
+fn brute_force(blueprint: &Blueprint, state: &State) -> u32 {
+    let objective = blueprint.objective;
+    if state.minutes_left == 0 {
+        return state.inventory[objective];
+    }
+    let mut options = vec![None];
+    for (robot, cost) in blueprint.costs.iter().enumerate() {
+        if let Some(cost) = cost {
+            if (0..cost.len()).all(|r| state.inventory[r] >= cost[r]) {
+                options.push(Some((robot, cost)));
+            }
+        }
+    }
+    options
+        .into_iter()
+        .map(|option| {
+            let mut next = state.clone();
+            next.minutes_left -= 1;
+            for resource in 0..next.inventory.len() {
+                next.inventory[resource] += state.robots[resource];
+            }
+            if let Some((robot, cost)) = option {
+                for resource in 0..next.inventory.len() {
+                    next.inventory[resource] -= cost[resource];
+                }
+                next.robots[robot] += 1;
+            }
+            brute_force(blueprint, &next)
+        })
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn matches_brute_force() {
+    let lines = "robot ore costs {ore: 2}
+robot clay costs {ore: 2}
+
+robot ore costs {ore: 3}
+robot clay costs {ore: 1}
+robot geode costs {ore: 2, clay: 2}
+
+start {ore: 1, wood: 1}
+robot wood costs {wood: 3}
+robot chair costs {wood: 4}
+robot table costs {wood: 2, chair: 2}"
+        .lines()
+        .map(String::from)
+        .collect::<Vec<String>>();
+    let mut blueprints = get_blueprints(&lines);
+    blueprints[0].set_objective(0);
+    assert_eq!(optimize(&blueprints[0], 14).amount, 51);
+    for mut blueprint in blueprints {
+        for objective in 0..blueprint.resources.len() {
+            blueprint.set_objective(objective);
+            for minutes in [6, 10] {
+                let start = State {
+                    minutes_left: minutes,
+                    inventory: vec![0; blueprint.resources.len()],
+                    robots: blueprint.start.clone(),
+                };
+                assert_eq!(
+                    optimize(&blueprint, minutes).amount,
+                    brute_force(&blueprint, &start),
+                    "{:?} maximising {} over {} minutes",
+                    blueprint.resources,
+                    blueprint.resources[objective],
+                    minutes
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::utils::random::XorShift;
+
+const NIL: usize = usize::MAX;
+
+// Implicit treap over the numbers' original indices, keyed by position in the
+// mixed sequence. Parent links let us find where a number currently sits.
+struct Treap {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    parent: Vec<usize>,
+    priority: Vec<u64>,
+    size: Vec<usize>,
+    root: usize,
+}
+
+impl Treap {
+    fn new(len: usize) -> Treap {
+        let mut rng = XorShift::new(0x2545_f491_4f6c_dd1d);
+        let priority = (0..len).map(|_| rng.next_u64()).collect();
+        let mut treap = Treap {
+            left: vec![NIL; len],
+            right: vec![NIL; len],
+            parent: vec![NIL; len],
+            priority,
+            size: vec![1; len],
+            root: NIL,
+        };
+        for node in 0..len {
+            treap.root = treap.merge(treap.root, node);
+        }
+        treap
+    }
+
+    fn get_size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.size[node]
+        }
+    }
+
+    fn update(&mut self, node: usize) {
+        let (left, right) = (self.left[node], self.right[node]);
+        self.size[node] = 1 + self.get_size(left) + self.get_size(right);
+        for child in [left, right] {
+            if child != NIL {
+                self.parent[child] = node;
+            }
+        }
+    }
+
+    // Splits off the first `count` elements. The returned roots keep stale
+    // parent links until the caller fixes them up.
+    fn split(&mut self, node: usize, count: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+        let left = self.left[node];
+        if self.get_size(left) >= count {
+            let (a, b) = self.split(left, count);
+            self.left[node] = b;
+            self.update(node);
+            (a, node)
+        } else {
+            let skip = count - self.get_size(left) - 1;
+            let (a, b) = self.split(self.right[node], skip);
+            self.right[node] = a;
+            self.update(node);
+            (node, b)
+        }
+    }
+
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL || b == NIL {
+            return if a == NIL { b } else { a };
+        }
+        if self.priority[a] > self.priority[b] {
+            self.right[a] = self.merge(self.right[a], b);
+            self.update(a);
+            a
+        } else {
+            self.left[b] = self.merge(a, self.left[b]);
+            self.update(b);
+            b
+        }
+    }
+
+    fn set_root(&mut self, root: usize) {
+        self.root = root;
+        if root != NIL {
+            self.parent[root] = NIL;
+        }
+    }
+
+    fn position(&self, node: usize) -> usize {
+        let mut position = self.get_size(self.left[node]);
+        let mut node = node;
+        while self.parent[node] != NIL {
+            let parent = self.parent[node];
+            if self.right[parent] == node {
+                position += self.get_size(self.left[parent]) + 1;
+            }
+            node = parent;
+        }
+        position
+    }
+
+    fn remove(&mut self, position: usize) -> usize {
+        let (a, b) = self.split(self.root, position);
+        let (node, c) = self.split(b, 1);
+        let root = self.merge(a, c);
+        self.set_root(root);
+        self.parent[node] = NIL;
+        node
+    }
+
+    fn insert(&mut self, position: usize, node: usize) {
+        let (a, b) = self.split(self.root, position);
+        let a = self.merge(a, node);
+        let root = self.merge(a, b);
+        self.set_root(root);
+    }
+
+    fn get_order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.size.len());
+        let mut stack = Vec::new();
+        let mut node = self.root;
+        while node != NIL || !stack.is_empty() {
+            while node != NIL {
+                stack.push(node);
+                node = self.left[node];
+            }
+            node = stack.pop().unwrap();
+            order.push(node);
+            node = self.right[node];
+        }
+        order
+    }
+}
+
+pub fn get_numbers(lines: &[String]) -> Vec<i64> {
+    lines.iter().map(|line| line.parse().unwrap()).collect()
+}
+
+// Moving a number round the circle past the other `len - 1` numbers brings it
+// back where it started, so each move is reduced modulo that before inserting.
+pub fn mix(numbers: &[i64], key: i64, rounds: usize) -> Vec<i64> {
+    let values = numbers.iter().map(|n| n * key).collect::<Vec<i64>>();
+    if values.len() < 2 {
+        return values;
+    }
+    let cycle = values.len() as i64 - 1;
+    let mut treap = Treap::new(values.len());
+    for _ in 0..rounds {
+        for (node, value) in values.iter().enumerate() {
+            let position = treap.position(node);
+            treap.remove(position);
+            let target = (position as i64 + value).rem_euclid(cycle);
+            treap.insert(target as usize, node);
+        }
+    }
+    treap
+        .get_order()
+        .into_iter()
+        .map(|node| values[node])
+        .collect()
+}
+
+pub fn get_coordinates(mixed: &[i64]) -> i64 {
+    let zero = mixed.iter().position(|n| *n == 0).unwrap();
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+// The original part 2 mixer, kept as an oracle for the treap.
+#[cfg(test)]
+struct Number {
+    value: isize,
+    index: usize,
+}
+
+#[cfg(test)]
+fn move_numbers(numbers: &mut Vec<Number>) {
+    let len = numbers.len();
+    for i in 0..len {
+        let pos = numbers.iter().position(|number| number.index == i).unwrap();
+        if numbers[pos].value == 0 {
+            continue;
+        }
+        let number = numbers.remove(pos);
+        let mut new_pos = pos as isize + number.value;
+        if new_pos <= 0 {
+            let mul = (new_pos.abs() / (len as isize - 1)) + 1;
+            new_pos += mul * (len as isize - 1);
+        }
+        if new_pos >= len as isize {
+            let mul = new_pos / (len as isize - 1);
+            new_pos -= mul * (len as isize - 1);
+        }
+        numbers.insert(new_pos as usize, number);
+    }
+}
+
+#[cfg(test)]
+fn mix_slowly(numbers: &[i64], key: i64, rounds: usize) -> Vec<i64> {
+    let mut mixed = numbers
+        .iter()
+        .enumerate()
+        .map(|(index, n)| Number {
+            value: (n * key) as isize,
+            index,
+        })
+        .collect::<Vec<Number>>();
+    for _ in 0..rounds {
+        move_numbers(&mut mixed);
+    }
+    mixed
+        .into_iter()
+        .map(|number| number.value as i64)
+        .collect()
+}
+
+#[cfg(test)]
+fn from_zero(mixed: &[i64]) -> Vec<i64> {
+    let zero = mixed.iter().position(|n| *n == 0).unwrap();
+    [&mixed[zero..], &mixed[..zero]].concat()
+}
+
+#[test]
+fn example() {
+    let numbers = vec![1, 2, -3, 3, -2, 0, 4];
+    let mixed = mix(&numbers, 1, 1);
+    assert_eq!(from_zero(&mixed), vec![0, 3, -2, 1, 2, -3, 4]);
+    assert_eq!(get_coordinates(&mixed), 3);
+    assert_eq!(get_coordinates(&mix(&numbers, 811589153, 10)), 1623178306);
+}
+
+#[test]
+fn matches_slow_mix() {
+    let mut rng = XorShift::new(7);
+    let mut numbers = (0..300)
+        .map(|_| rng.between(-1000, 1000))
+        .filter(|n| *n != 0)
+        .collect::<Vec<i64>>();
+    numbers.insert(numbers.len() / 2, 0);
+    for (key, rounds) in [(1, 1), (811589153, 10), (-3, 4)] {
+        assert_eq!(
+            from_zero(&mix(&numbers, key, rounds)),
+            from_zero(&mix_slowly(&numbers, key, rounds))
+        );
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::utils::rational::Rational;
+
+pub const ROOT: &str = "root";
+pub const HUMAN: &str = "humn";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Add,
+    Mul,
+    Sub,
+    Div,
+    Ret,
+}
+
+impl Operation {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operation::Add => "+",
+            Operation::Mul => "*",
+            Operation::Sub => "-",
+            Operation::Div => "/",
+            Operation::Ret => "",
+        }
+    }
+}
+
+// A monkey with its operands resolved to node indices. Monkeys may share
+// operands, so the nodes form a DAG rather than a tree.
+#[derive(Debug, Clone)]
+pub struct FlatNode {
+    pub name: String,
+    pub value: i64,
+    pub children: Vec<usize>,
+    pub operation: Operation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    UnknownMonkey(String, String),
+    Cycle(String),
+    Nonlinear(String),
+    DivisionByZero(String),
+    Overflow(String),
+    NoSolution,
+    AnySolution,
+    NotInteger(Rational),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::UnknownMonkey(name, operand) => {
+                write!(f, "{}: no monkey called '{}'", name, operand)
+            }
+            SolveError::Cycle(name) => write!(f, "{}: depends on itself", name),
+            SolveError::Nonlinear(name) => write!(f, "{}: not linear in the unknown", name),
+            SolveError::DivisionByZero(name) => write!(f, "{}: division by zero", name),
+            SolveError::Overflow(name) => write!(f, "{}: arithmetic overflow", name),
+            SolveError::NoSolution => write!(f, "the equation has no solution"),
+            SolveError::AnySolution => write!(f, "every value solves the equation"),
+            SolveError::NotInteger(value) => write!(f, "the solution {} is not an integer", value),
+        }
+    }
+}
+
+// `coefficient * x + constant` for the single unknown x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear {
+    pub coefficient: Rational,
+    pub constant: Rational,
+}
+
+impl Linear {
+    pub fn constant(value: Rational) -> Linear {
+        Linear {
+            coefficient: Rational::ZERO,
+            constant: value,
+        }
+    }
+
+    pub fn unknown() -> Linear {
+        Linear {
+            coefficient: Rational::from_integer(1),
+            constant: Rational::ZERO,
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    fn add(&self, other: &Linear) -> Option<Linear> {
+        Some(Linear {
+            coefficient: self.coefficient.add(&other.coefficient)?,
+            constant: self.constant.add(&other.constant)?,
+        })
+    }
+
+    fn subtract(&self, other: &Linear) -> Option<Linear> {
+        Some(Linear {
+            coefficient: self.coefficient.subtract(&other.coefficient)?,
+            constant: self.constant.subtract(&other.constant)?,
+        })
+    }
+
+    fn scale(&self, factor: &Rational) -> Option<Linear> {
+        Some(Linear {
+            coefficient: self.coefficient.multiply(factor)?,
+            constant: self.constant.multiply(factor)?,
+        })
+    }
+
+    fn apply(
+        operation: Operation,
+        a: &Linear,
+        b: &Linear,
+        name: &str,
+    ) -> Result<Linear, SolveError> {
+        let result = match operation {
+            Operation::Add => a.add(b),
+            Operation::Sub => a.subtract(b),
+            Operation::Mul if b.is_constant() => a.scale(&b.constant),
+            Operation::Mul if a.is_constant() => b.scale(&a.constant),
+            Operation::Div if b.is_constant() => {
+                if b.constant.is_zero() {
+                    return Err(SolveError::DivisionByZero(name.to_string()));
+                }
+                Rational::from_integer(1)
+                    .divide(&b.constant)
+                    .and_then(|inverse| a.scale(&inverse))
+            }
+            Operation::Mul | Operation::Div => return Err(SolveError::Nonlinear(name.to_string())),
+            Operation::Ret => unreachable!(),
+        };
+        result.ok_or_else(|| SolveError::Overflow(name.to_string()))
+    }
+}
+
+impl fmt::Display for Linear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_constant() {
+            write!(f, "{}", self.constant)
+        } else {
+            write!(f, "{}x + {}", self.coefficient, self.constant)
+        }
+    }
+}
+
+pub struct Graph {
+    pub nodes: Vec<FlatNode>,
+    pub indices: HashMap<String, usize>,
+}
+
+impl Graph {
+    pub fn parse(lines: &[String]) -> Result<Graph, SolveError> {
+        let mut nodes = Vec::new();
+        let mut operands = Vec::new();
+        for line in lines {
+            let mut parts = line.split(' ');
+            let name = parts.next().unwrap().replace(':', "");
+            let first = parts.next().unwrap();
+            let operation = match parts.next() {
+                None => Operation::Ret,
+                Some("+") => Operation::Add,
+                Some("*") => Operation::Mul,
+                Some("-") => Operation::Sub,
+                Some("/") => Operation::Div,
+                Some(operation) => panic!("Unknown operation: {}", operation),
+            };
+            let value = if operation == Operation::Ret {
+                first.parse().unwrap()
+            } else {
+                operands.push((nodes.len(), [first, parts.next().unwrap()]));
+                0
+            };
+            nodes.push(FlatNode {
+                name,
+                value,
+                children: Vec::new(),
+                operation,
+            });
+        }
+        let indices = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.name.clone(), index))
+            .collect::<HashMap<String, usize>>();
+        for (index, names) in operands {
+            for name in names {
+                let child = *indices.get(name).ok_or_else(|| {
+                    SolveError::UnknownMonkey(nodes[index].name.clone(), name.to_string())
+                })?;
+                nodes[index].children.push(child);
+            }
+        }
+        Ok(Graph { nodes, indices })
+    }
+
+    pub fn get_index(&self, name: &str) -> Result<usize, SolveError> {
+        self.indices
+            .get(name)
+            .copied()
+            .ok_or_else(|| SolveError::UnknownMonkey(name.to_string(), name.to_string()))
+    }
+
+    // Folds every node reachable from `root` into a linear expression in the
+    // unknown, leaving unreachable nodes as None. Shared operands are only
+    // evaluated once.
+    pub fn get_values(
+        &self,
+        root: usize,
+        unknown: Option<usize>,
+    ) -> Result<Vec<Option<Linear>>, SolveError> {
+        let mut values = vec![None; self.nodes.len()];
+        let mut visiting = vec![false; self.nodes.len()];
+        self.visit(root, unknown, &mut values, &mut visiting)?;
+        Ok(values)
+    }
+
+    fn visit(
+        &self,
+        index: usize,
+        unknown: Option<usize>,
+        values: &mut Vec<Option<Linear>>,
+        visiting: &mut Vec<bool>,
+    ) -> Result<Linear, SolveError> {
+        if let Some(value) = values[index] {
+            return Ok(value);
+        }
+        let node = &self.nodes[index];
+        if visiting[index] {
+            return Err(SolveError::Cycle(node.name.clone()));
+        }
+        visiting[index] = true;
+        let value = if Some(index) == unknown {
+            Linear::unknown()
+        } else if node.operation == Operation::Ret {
+            Linear::constant(Rational::from_integer(node.value))
+        } else {
+            let a = self.visit(node.children[0], unknown, values, visiting)?;
+            let b = self.visit(node.children[1], unknown, values, visiting)?;
+            Linear::apply(node.operation, &a, &b, &node.name)?
+        };
+        visiting[index] = false;
+        values[index] = Some(value);
+        Ok(value)
+    }
+
+    pub fn evaluate(&self, name: &str) -> Result<i64, SolveError> {
+        let index = self.get_index(name)?;
+        let value = self.get_values(index, None)?[index].unwrap().constant;
+        value.to_integer().ok_or(SolveError::NotInteger(value))
+    }
+
+    // Treats `root` as an equality between its operands and solves it for the
+    // monkey called `unknown`.
+    pub fn solve(&self, unknown: &str) -> Result<i64, SolveError> {
+        let root = self.get_index(ROOT)?;
+        let values = self.get_values(root, Some(self.get_index(unknown)?))?;
+        let [left, right] = [0, 1].map(|side| values[self.nodes[root].children[side]].unwrap());
+        let overflow = || SolveError::Overflow(ROOT.to_string());
+        let equation = left.subtract(&right).ok_or_else(overflow)?;
+        if equation.is_constant() {
+            return Err(if equation.constant.is_zero() {
+                SolveError::AnySolution
+            } else {
+                SolveError::NoSolution
+            });
+        }
+        let solution = equation
+            .constant
+            .negate()
+            .and_then(|constant| constant.divide(&equation.coefficient))
+            .ok_or_else(overflow)?;
+        solution
+            .to_integer()
+            .ok_or(SolveError::NotInteger(solution))
+    }
+
+    // Marks every node whose value depends on `target`.
+    fn get_dependents(&self, root: usize, target: usize) -> Vec<bool> {
+        let mut depends = vec![false; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = vec![(root, false)];
+        while let Some((index, expanded)) = stack.pop() {
+            if expanded {
+                depends[index] = index == target
+                    || self.nodes[index]
+                        .children
+                        .iter()
+                        .any(|child| depends[*child]);
+                continue;
+            }
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            stack.push((index, true));
+            stack.extend(
+                self.nodes[index]
+                    .children
+                    .iter()
+                    .map(|child| (*child, false)),
+            );
+        }
+        depends
+    }
+
+    // Graphviz digraph of everything reachable from `root`, labelled with each
+    // monkey's operation and value. Edges on the way to `highlight` are drawn
+    // in red, and with `prune` any other operation is folded into a single
+    // node showing its value.
+    pub fn render_dot(&self, highlight: Option<&str>, prune: bool) -> Result<String, SolveError> {
+        let root = self.get_index(ROOT)?;
+        let values = self.get_values(root, None)?;
+        let depends = match highlight {
+            Some(name) => self.get_dependents(root, self.get_index(name)?),
+            None => vec![false; self.nodes.len()],
+        };
+        let mut dot = String::from("digraph monkeys {\n    node [shape=box];\n");
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = vec![root];
+        while let Some(index) = stack.pop() {
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            let node = &self.nodes[index];
+            let value = values[index].unwrap().constant;
+            let folded = prune && index != root && !depends[index];
+            let mut attributes = if node.operation == Operation::Ret || folded {
+                format!("label=\"{}\\n{}\"", node.name, value)
+            } else {
+                format!(
+                    "label=\"{}\\n{}\\n{}\"",
+                    node.name,
+                    node.operation.symbol(),
+                    value
+                )
+            };
+            if folded && node.operation != Operation::Ret {
+                attributes.push_str(", style=dashed");
+            }
+            if depends[index] {
+                attributes.push_str(", color=red");
+            }
+            dot.push_str(&format!("    \"{}\" [{}];\n", node.name, attributes));
+            if folded {
+                continue;
+            }
+            for child in &node.children {
+                let style = if depends[*child] { " [color=red]" } else { "" };
+                let name = &self.nodes[*child].name;
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\"{};\n",
+                    node.name, name, style
+                ));
+                stack.push(*child);
+            }
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+}
+
+#[cfg(test)]
+fn get_graph(text: &str) -> Result<Graph, SolveError> {
+    Graph::parse(&text.lines().map(String::from).collect::<Vec<String>>())
+}
+
+#[test]
+fn example() {
+    let graph = get_graph(
+        "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32",
+    )
+    .unwrap();
+    assert_eq!(graph.evaluate(ROOT), Ok(152));
+    assert_eq!(graph.solve(HUMAN), Ok(301));
+    let dot = graph.render_dot(Some(HUMAN), false).unwrap();
+    assert!(dot.starts_with("digraph monkeys {\n"));
+    assert!(dot.contains("    \"root\" [label=\"root\\n+\\n152\", color=red];\n"));
+    assert!(dot.contains("    \"ptdq\" -> \"humn\" [color=red];\n"));
+    assert!(dot.contains("    \"sjmn\" -> \"drzm\";\n"));
+    assert_eq!(dot.matches(" -> ").count(), 14);
+    let pruned = graph.render_dot(Some(HUMAN), true).unwrap();
+    assert!(pruned.contains("    \"sjmn\" [label=\"sjmn\\n150\", style=dashed];\n"));
+    assert!(!pruned.contains("drzm"));
+    assert_eq!(pruned.matches(" -> ").count(), 10);
+    // DOT keywords are valid monkey names.
+    let keywords = get_graph("root: node + edge\nnode: 1\nedge: humn * node\nhumn: 2").unwrap();
+    let dot = keywords.render_dot(Some(HUMAN), false).unwrap();
+    assert!(dot.contains("    \"root\" -> \"node\";\n"));
+    assert!(dot.contains("    \"edge\" [label=\"edge\\n*\\n2\", color=red];\n"));
+}
+
+#[test]
+fn errors() {
+    let solve = |text: &str| get_graph(text).and_then(|graph| graph.solve(HUMAN));
+    assert_eq!(
+        solve("root: humn + nope\nhumn: 1"),
+        Err(SolveError::UnknownMonkey(
+            String::from(ROOT),
+            String::from("nope")
+        ))
+    );
+    assert_eq!(
+        solve("root: sqre + four\nsqre: humn * humn\nfour: 4\nhumn: 1"),
+        Err(SolveError::Nonlinear(String::from("sqre")))
+    );
+    assert_eq!(
+        solve("root: dubl + thre\ndubl: humn * two\ntwo: 2\nthre: 3\nhumn: 1"),
+        Err(SolveError::NotInteger(Rational::new(3, 2).unwrap()))
+    );
+    assert_eq!(
+        solve("root: zero + one\nzero: humn - humn\none: 1\nhumn: 1"),
+        Err(SolveError::NoSolution)
+    );
+    // The unknown may appear more than once as long as it stays linear.
+    assert_eq!(
+        solve("root: twce + six\ntwce: humn + humn\nsix: 6\nhumn: 1"),
+        Ok(3)
+    );
+}
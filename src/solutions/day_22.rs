@@ -0,0 +1,517 @@
+use std::fmt;
+
+type Vector = [i32; 3];
+
+fn negate(v: Vector) -> Vector {
+    [-v[0], -v[1], -v[2]]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+// Facings in the order they are scored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+        Direction::Up,
+    ];
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn turn(self, turn: Turn) -> Direction {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    pub fn get_step(self) -> (isize, isize) {
+        match self {
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Up => (0, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Grid,
+    Air,
+    Wall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Movement {
+    pub steps: usize,
+    pub turn: Option<Turn>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub x: isize,
+    pub y: isize,
+    pub direction: Direction,
+}
+
+impl Position {
+    pub fn get_password(&self) -> isize {
+        1000 * (self.y + 1) + 4 * (self.x + 1) + self.direction as isize
+    }
+}
+
+pub struct Board {
+    pub rows: Vec<Vec<Square>>,
+}
+
+impl Board {
+    pub fn parse(lines: &[String]) -> Board {
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let rows = lines
+            .iter()
+            .map(|line| {
+                let mut row = line
+                    .chars()
+                    .map(|c| match c {
+                        '#' => Square::Wall,
+                        '.' => Square::Grid,
+                        ' ' => Square::Air,
+                        _ => panic!("Unknown square"),
+                    })
+                    .collect::<Vec<Square>>();
+                row.resize(width, Square::Air);
+                row
+            })
+            .collect();
+        Board { rows }
+    }
+
+    // Anything outside the rows counts as air.
+    pub fn get(&self, x: isize, y: isize) -> Square {
+        if x < 0 || y < 0 {
+            return Square::Air;
+        }
+        self.rows
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .unwrap_or(Square::Air)
+    }
+
+    pub fn get_start(&self) -> Position {
+        let x = self.rows[0]
+            .iter()
+            .position(|square| *square == Square::Grid)
+            .expect("No starting position found");
+        Position {
+            x: x as isize,
+            y: 0,
+            direction: Direction::Right,
+        }
+    }
+}
+
+pub fn get_movements(line: &str) -> Vec<Movement> {
+    let mut movements = Vec::new();
+    let mut steps = 0;
+    for c in line.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            steps = steps * 10 + digit as usize;
+            continue;
+        }
+        let turn = match c {
+            'L' => Turn::Left,
+            'R' => Turn::Right,
+            _ => panic!("Unknown turn"),
+        };
+        movements.push(Movement {
+            steps,
+            turn: Some(turn),
+        });
+        steps = 0;
+    }
+    movements.push(Movement { steps, turn: None });
+    movements
+}
+
+pub fn get_board_and_movements(lines: &[String]) -> (Board, Vec<Movement>) {
+    let separator = lines.iter().position(|line| line.is_empty()).unwrap();
+    (
+        Board::parse(&lines[..separator]),
+        get_movements(&lines[separator + 1]),
+    )
+}
+
+// How the edges of the board join up.
+pub trait Topology {
+    // Where a walker at `position` lands when its next step would leave the
+    // board.
+    fn wrap(&self, board: &Board, position: Position) -> Position;
+}
+
+// Stepping off an edge comes back in on the far side of the same row or
+// column.
+pub struct FlatWrap;
+
+impl Topology for FlatWrap {
+    fn wrap(&self, board: &Board, position: Position) -> Position {
+        let (dx, dy) = position.direction.get_step();
+        let (mut x, mut y) = (position.x, position.y);
+        while board.get(x - dx, y - dy) != Square::Air {
+            x -= dx;
+            y -= dy;
+        }
+        Position { x, y, ..position }
+    }
+}
+
+// Follows the movements, stopping short of walls, and returns where the walker
+// ends up.
+pub fn walk(board: &Board, topology: &impl Topology, movements: &[Movement]) -> Position {
+    let mut position = board.get_start();
+    for movement in movements {
+        for _ in 0..movement.steps {
+            let (dx, dy) = position.direction.get_step();
+            let mut next = Position {
+                x: position.x + dx,
+                y: position.y + dy,
+                ..position
+            };
+            if board.get(next.x, next.y) == Square::Air {
+                next = topology.wrap(board, position);
+            }
+            if board.get(next.x, next.y) == Square::Wall {
+                break;
+            }
+            position = next;
+        }
+        if let Some(turn) = movement.turn {
+            position.direction = position.direction.turn(turn);
+        }
+    }
+    position
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FoldError {
+    FaceSize(usize),
+    FaceCount(usize),
+    Overlap(usize, usize),
+}
+
+impl fmt::Display for FoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FoldError::FaceSize(cells) => {
+                write!(f, "{} cells cannot be split into six square faces", cells)
+            }
+            FoldError::FaceCount(faces) => write!(f, "the net has {} faces, not 6", faces),
+            FoldError::Overlap(x, y) => {
+                write!(f, "face at ({}, {}) folds onto another face", x, y)
+            }
+        }
+    }
+}
+
+// A face of the net, with the outward normal and the directions its map
+// right and down point in once folded.
+#[derive(Debug, Clone, Copy)]
+pub struct Face {
+    pub x: usize,
+    pub y: usize,
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+impl Face {
+    fn get_edge(&self, direction: Direction) -> Vector {
+        match direction {
+            Direction::Right => self.right,
+            Direction::Down => self.down,
+            Direction::Left => negate(self.right),
+            Direction::Up => negate(self.down),
+        }
+    }
+
+    // The way offsets along an edge run: downwards for the sides and
+    // rightwards for the top and bottom.
+    fn get_tangent(&self, direction: Direction) -> Vector {
+        match direction {
+            Direction::Right | Direction::Left => self.down,
+            Direction::Down | Direction::Up => self.right,
+        }
+    }
+
+    // Rolls the cube over the edge in `direction`, giving the frame of the
+    // face that ends up on top.
+    fn roll(&self, direction: Direction, x: usize, y: usize) -> Face {
+        let (normal, right, down) = match direction {
+            Direction::Right => (self.right, negate(self.normal), self.down),
+            Direction::Left => (negate(self.right), self.normal, self.down),
+            Direction::Down => (self.down, self.right, negate(self.normal)),
+            Direction::Up => (negate(self.down), self.right, self.normal),
+        };
+        Face {
+            x,
+            y,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+// The board folded into a cube, so stepping off a face's edge continues on
+// whichever face shares that edge.
+pub struct Cube {
+    pub size: usize,
+    pub faces: Vec<Face>,
+}
+
+impl Cube {
+    // Faces are assigned 3D orientations by rolling a cube across the net from
+    // the first face.
+    pub fn fold(board: &Board) -> Result<Cube, FoldError> {
+        let cells = board
+            .rows
+            .iter()
+            .flatten()
+            .filter(|square| **square != Square::Air)
+            .count();
+        let size = (1..=cells).find(|n| 6 * n * n >= cells).unwrap_or(0);
+        if cells == 0 || 6 * size * size != cells {
+            return Err(FoldError::FaceSize(cells));
+        }
+        let is_face =
+            |x: usize, y: usize| board.get((x * size) as isize, (y * size) as isize) != Square::Air;
+        let height = board.rows.len().div_ceil(size);
+        let width = board.rows[0].len().div_ceil(size);
+        let positions = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|(x, y)| is_face(*x, *y))
+            .collect::<Vec<(usize, usize)>>();
+        if positions.len() != 6 {
+            return Err(FoldError::FaceCount(positions.len()));
+        }
+
+        let (x, y) = positions[0];
+        let mut faces = vec![Face {
+            x,
+            y,
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }];
+        let mut next = 0;
+        while next < faces.len() {
+            let face = faces[next];
+            next += 1;
+            for direction in Direction::ALL {
+                let (dx, dy) = direction.get_step();
+                let (x, y) = (face.x as isize + dx, face.y as isize + dy);
+                if x < 0 || y < 0 || !is_face(x as usize, y as usize) {
+                    continue;
+                }
+                let (x, y) = (x as usize, y as usize);
+                if faces.iter().any(|f| (f.x, f.y) == (x, y)) {
+                    continue;
+                }
+                let rolled = face.roll(direction, x, y);
+                if faces.iter().any(|f| f.normal == rolled.normal) {
+                    return Err(FoldError::Overlap(x, y));
+                }
+                faces.push(rolled);
+            }
+        }
+        if faces.len() != 6 {
+            return Err(FoldError::FaceCount(faces.len()));
+        }
+        Ok(Cube { size, faces })
+    }
+
+    // Board cell on the edge of a face, `offset` cells along its tangent.
+    fn get_edge_cell(&self, face: &Face, direction: Direction, offset: usize) -> (isize, isize) {
+        let last = self.size - 1;
+        let (x, y) = match direction {
+            Direction::Right => (last, offset),
+            Direction::Down => (offset, last),
+            Direction::Left => (0, offset),
+            Direction::Up => (offset, 0),
+        };
+        (
+            (face.x * self.size + x) as isize,
+            (face.y * self.size + y) as isize,
+        )
+    }
+}
+
+impl Topology for Cube {
+    fn wrap(&self, _: &Board, position: Position) -> Position {
+        let (x, y) = (position.x as usize, position.y as usize);
+        let face = self
+            .faces
+            .iter()
+            .find(|f| (f.x, f.y) == (x / self.size, y / self.size))
+            .unwrap();
+        let direction = position.direction;
+        let edge = face.get_edge(direction);
+        let target = self.faces.iter().find(|f| f.normal == edge).unwrap();
+        let entry = Direction::ALL
+            .into_iter()
+            .find(|d| target.get_edge(*d) == face.normal)
+            .unwrap();
+        let offset = match direction {
+            Direction::Right | Direction::Left => y % self.size,
+            Direction::Down | Direction::Up => x % self.size,
+        };
+        let landing = if face.get_tangent(direction) == target.get_tangent(entry) {
+            offset
+        } else {
+            self.size - 1 - offset
+        };
+        let (x, y) = self.get_edge_cell(target, entry, landing);
+        Position {
+            x,
+            y,
+            direction: entry.reverse(),
+        }
+    }
+}
+
+#[cfg(test)]
+fn example() -> Vec<String> {
+    [
+        "        ...#",
+        "        .#..",
+        "        #...",
+        "        ....",
+        "...#.......#",
+        "........#...",
+        "..#....#....",
+        "..........#.",
+        "        ...#....",
+        "        .....#..",
+        "        .#......",
+        "        ......#.",
+        "",
+        "10R5L5R10L4R5L5",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+#[test]
+fn example_walk() {
+    let (board, movements) = get_board_and_movements(&example());
+    assert_eq!(walk(&board, &FlatWrap, &movements).get_password(), 6032);
+    let cube = Cube::fold(&board).unwrap();
+    assert_eq!(cube.size, 4);
+    let position = |x, y, direction| Position { x, y, direction };
+    assert_eq!(
+        cube.wrap(&board, position(11, 5, Direction::Right)),
+        position(14, 8, Direction::Down)
+    );
+    assert_eq!(
+        cube.wrap(&board, position(10, 11, Direction::Down)),
+        position(1, 7, Direction::Up)
+    );
+    assert_eq!(walk(&board, &cube, &movements).get_password(), 5031);
+}
+
+#[cfg(test)]
+fn get_net(text: &str, scale: usize) -> Board {
+    let lines = text
+        .lines()
+        .flat_map(|line| {
+            let row = line
+                .chars()
+                .flat_map(|c| std::iter::repeat_n(if c == '#' { '.' } else { ' ' }, scale))
+                .collect::<String>();
+            std::iter::repeat_n(row, scale)
+        })
+        .collect::<Vec<String>>();
+    Board::parse(&lines)
+}
+
+#[test]
+fn all_nets() {
+    let nets = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+    for net in nets {
+        for size in [1, 3] {
+            let board = get_net(net, size);
+            let cube = Cube::fold(&board).unwrap();
+            // Crossing any edge and turning straight back returns us to where
+            // we left, facing the other way.
+            for face in &cube.faces {
+                for direction in Direction::ALL {
+                    for offset in 0..size {
+                        let (x, y) = cube.get_edge_cell(face, direction, offset);
+                        let landing = cube.wrap(&board, Position { x, y, direction });
+                        assert_eq!(board.get(landing.x, landing.y), Square::Grid);
+                        let back = Position {
+                            direction: landing.direction.reverse(),
+                            ..landing
+                        };
+                        assert_eq!(
+                            cube.wrap(&board, back),
+                            Position {
+                                x,
+                                y,
+                                direction: direction.reverse()
+                            }
+                        );
+                    }
+                }
+            }
+        }
+    }
+    assert_eq!(
+        Cube::fold(&get_net("######", 1)).err(),
+        Some(FoldError::Overlap(4, 0))
+    );
+    assert_eq!(
+        Cube::fold(&get_net("###\n###", 1)).err(),
+        Some(FoldError::Overlap(1, 1))
+    );
+    assert_eq!(
+        Cube::fold(&get_net("####", 1)).err(),
+        Some(FoldError::FaceSize(4))
+    );
+}
@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+pub struct Markers<I: Iterator<Item = u8>> {
+    bytes: I,
+    window_size: usize,
+    window: VecDeque<u8>,
+    counts: [usize; 256],
+    distinct: usize,
+    position: usize,
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Markers<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for byte in self.bytes.by_ref() {
+            self.position += 1;
+            if self.window.len() == self.window_size {
+                let old = self.window.pop_front().unwrap();
+                self.counts[old as usize] -= 1;
+                if self.counts[old as usize] == 0 {
+                    self.distinct -= 1;
+                }
+            }
+            self.window.push_back(byte);
+            self.counts[byte as usize] += 1;
+            if self.counts[byte as usize] == 1 {
+                self.distinct += 1;
+            }
+            if self.distinct == self.window_size {
+                return Some(self.position);
+            }
+        }
+        None
+    }
+}
+
+// Yields the position just after every window of `window_size` distinct bytes.
+pub fn get_markers<I: IntoIterator<Item = u8>>(
+    bytes: I,
+    window_size: usize,
+) -> Markers<I::IntoIter> {
+    assert!(window_size > 0, "Window size must be positive");
+    Markers {
+        bytes: bytes.into_iter(),
+        window_size,
+        window: VecDeque::with_capacity(window_size),
+        counts: [0; 256],
+        distinct: 0,
+        position: 0,
+    }
+}
+
+pub fn get_markers_from_reader<R: BufRead>(
+    reader: R,
+    window_size: usize,
+) -> Markers<impl Iterator<Item = u8>> {
+    let bytes = reader
+        .bytes()
+        .map(|b| b.unwrap())
+        .take_while(|b| *b != b'\n');
+    get_markers(bytes, window_size)
+}
+
+#[test]
+fn example() {
+    let packet = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    assert_eq!(get_markers(packet.bytes(), 4).next(), Some(7));
+    assert_eq!(get_markers(packet.bytes(), 14).next(), Some(19));
+    assert_eq!(
+        get_markers("abcab".bytes(), 3).collect::<Vec<usize>>(),
+        vec![3, 4, 5]
+    );
+}
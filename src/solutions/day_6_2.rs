@@ -1,21 +1,12 @@
-use crate::utils::files::get_data_as_lines;
-
-fn all_chars_are_different(chars: &[char]) -> bool {
-    let mut chars = chars.to_owned();
-    chars.sort();
-    chars.dedup();
-    chars.len() == chars.capacity()
-}
+use crate::solutions::day_6::get_markers_from_reader;
+use crate::utils::files::get_data_as_reader;
 
 pub fn solve() -> String {
-    let lines = get_data_as_lines("day_6_packet.txt");
-    let chars = &lines[0].chars().collect::<Vec<char>>();
-    for i in 0..chars.len() {
-        if all_chars_are_different(&chars[i..i + 14]) {
-            return (i + 14).to_string();
-        }
+    let reader = get_data_as_reader("day_6_packet.txt");
+    match get_markers_from_reader(reader, 14).next() {
+        Some(marker) => marker.to_string(),
+        None => panic!("No solution found"),
     }
-    panic!("No solution found");
 }
 
 #[test]
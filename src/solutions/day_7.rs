@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptError {
+    UnknownDirectory(usize, String),
+    AboveRoot(usize),
+    OutputWithoutListing(usize),
+    ConflictingEntry(usize, String),
+    InvalidLine(usize, String),
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranscriptError::UnknownDirectory(line, path) => {
+                write!(f, "line {}: no such directory '{}'", line, path)
+            }
+            TranscriptError::AboveRoot(line) => write!(f, "line {}: cannot leave root", line),
+            TranscriptError::OutputWithoutListing(line) => {
+                write!(f, "line {}: output without a preceding ls", line)
+            }
+            TranscriptError::ConflictingEntry(line, name) => {
+                write!(
+                    f,
+                    "line {}: '{}' conflicts with an earlier listing",
+                    line, name
+                )
+            }
+            TranscriptError::InvalidLine(line, text) => {
+                write!(f, "line {}: cannot parse '{}'", line, text)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Directory {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub children: BTreeMap<String, usize>,
+    pub files: BTreeMap<String, u64>,
+}
+
+// Directories are stored in an arena, the root is always at index 0 and every
+// child is created after its parent.
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    pub directories: Vec<Directory>,
+}
+
+impl FileSystem {
+    pub const ROOT: usize = 0;
+
+    pub fn new() -> FileSystem {
+        FileSystem {
+            directories: vec![Directory {
+                name: String::from("/"),
+                parent: None,
+                children: BTreeMap::new(),
+                files: BTreeMap::new(),
+            }],
+        }
+    }
+
+    pub fn from_transcript(lines: &[String]) -> Result<FileSystem, TranscriptError> {
+        let mut fs = FileSystem::new();
+        let mut cwd = FileSystem::ROOT;
+        let mut listing = false;
+        for (i, line) in lines.iter().enumerate() {
+            let number = i + 1;
+            let parts = line.split(' ').collect::<Vec<&str>>();
+            match parts[..] {
+                ["$", "ls"] => listing = true,
+                ["$", "cd", path] => {
+                    listing = false;
+                    cwd = fs.resolve(cwd, path).map_err(|e| match e {
+                        None => TranscriptError::AboveRoot(number),
+                        Some(_) => TranscriptError::UnknownDirectory(number, path.to_string()),
+                    })?;
+                }
+                ["dir", name] if listing => {
+                    if fs.directories[cwd].files.contains_key(name) {
+                        return Err(TranscriptError::ConflictingEntry(number, name.to_string()));
+                    }
+                    fs.add_directory(cwd, name);
+                }
+                [size, name] if listing && size != "$" && size != "dir" => {
+                    let size = size
+                        .parse::<u64>()
+                        .map_err(|_| TranscriptError::InvalidLine(number, line.to_string()))?;
+                    let directory = &mut fs.directories[cwd];
+                    if directory.children.contains_key(name)
+                        || directory.files.get(name).is_some_and(|s| *s != size)
+                    {
+                        return Err(TranscriptError::ConflictingEntry(number, name.to_string()));
+                    }
+                    directory.files.insert(name.to_string(), size);
+                }
+                [first, _] if !listing && first != "$" => {
+                    return Err(TranscriptError::OutputWithoutListing(number));
+                }
+                _ => return Err(TranscriptError::InvalidLine(number, line.to_string())),
+            }
+        }
+        Ok(fs)
+    }
+
+    pub fn add_directory(&mut self, parent: usize, name: &str) -> usize {
+        if let Some(existing) = self.directories[parent].children.get(name) {
+            return *existing;
+        }
+        let index = self.directories.len();
+        self.directories.push(Directory {
+            name: name.to_string(),
+            parent: Some(parent),
+            children: BTreeMap::new(),
+            files: BTreeMap::new(),
+        });
+        self.directories[parent]
+            .children
+            .insert(name.to_string(), index);
+        index
+    }
+
+    // Resolves an absolute or relative path. On failure returns the component
+    // that could not be found, or `None` when the path climbs above the root.
+    pub fn resolve(&self, cwd: usize, path: &str) -> Result<usize, Option<String>> {
+        let mut current = if path.starts_with('/') {
+            FileSystem::ROOT
+        } else {
+            cwd
+        };
+        for component in path.split('/').filter(|c| !c.is_empty() && *c != ".") {
+            current = match component {
+                ".." => self.directories[current].parent.ok_or(None)?,
+                name => *self.directories[current]
+                    .children
+                    .get(name)
+                    .ok_or_else(|| Some(name.to_string()))?,
+            };
+        }
+        Ok(current)
+    }
+
+    pub fn path(&self, directory: usize) -> String {
+        let mut names = Vec::new();
+        let mut current = directory;
+        while let Some(parent) = self.directories[current].parent {
+            names.push(self.directories[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    // Total size of every directory, indexed like `directories`.
+    pub fn get_sizes(&self) -> Vec<u64> {
+        let mut sizes = self
+            .directories
+            .iter()
+            .map(|d| d.files.values().sum())
+            .collect::<Vec<u64>>();
+        for i in (1..self.directories.len()).rev() {
+            let parent = self.directories[i].parent.unwrap();
+            sizes[parent] += sizes[i];
+        }
+        sizes
+    }
+
+    // Size of the smallest directory whose deletion leaves `needed` free on a
+    // disk of `capacity`, or 0 if there is already enough room.
+    pub fn get_deletion_size(&self, capacity: u64, needed: u64) -> u64 {
+        let sizes = self.get_sizes();
+        let required = (sizes[FileSystem::ROOT] + needed).saturating_sub(capacity);
+        if required == 0 {
+            return 0;
+        }
+        sizes
+            .into_iter()
+            .filter(|size| *size >= required)
+            .min()
+            .unwrap()
+    }
+
+    pub fn render_tree(&self) -> String {
+        let mut output = String::new();
+        self.render_directory(FileSystem::ROOT, 0, &mut output);
+        output
+    }
+
+    fn render_directory(&self, directory: usize, depth: usize, output: &mut String) {
+        let indent = "  ".repeat(depth);
+        let dir = &self.directories[directory];
+        output.push_str(&format!("{}- {} (dir)\n", indent, dir.name));
+        let mut entries = dir
+            .children
+            .iter()
+            .map(|(name, index)| (name, Some(*index), 0))
+            .chain(dir.files.iter().map(|(name, size)| (name, None, *size)))
+            .collect::<Vec<(&String, Option<usize>, u64)>>();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, child, size) in entries {
+            match child {
+                Some(child) => self.render_directory(child, depth + 1, output),
+                None => output.push_str(&format!("{}  - {} (file, size={})\n", indent, name, size)),
+            }
+        }
+    }
+
+    // One line per directory in post-order, like `du`.
+    pub fn render_du(&self) -> String {
+        let sizes = self.get_sizes();
+        let mut order = Vec::new();
+        self.post_order(FileSystem::ROOT, &mut order);
+        order
+            .iter()
+            .map(|d| format!("{}\t{}\n", sizes[*d], self.path(*d)))
+            .collect()
+    }
+
+    fn post_order(&self, directory: usize, order: &mut Vec<usize>) {
+        for child in self.directories[directory].children.values() {
+            self.post_order(*child, order);
+        }
+        order.push(directory);
+    }
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem::new()
+    }
+}
+
+#[cfg(test)]
+fn example() -> FileSystem {
+    let transcript = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
+    let lines = transcript
+        .lines()
+        .map(String::from)
+        .collect::<Vec<String>>();
+    FileSystem::from_transcript(&lines).unwrap()
+}
+
+#[test]
+fn example_sizes() {
+    let fs = example();
+    let sizes = fs.get_sizes();
+    assert_eq!(sizes[FileSystem::ROOT], 48381165);
+    assert_eq!(sizes[fs.resolve(0, "/a/e").unwrap()], 584);
+    assert_eq!(fs.resolve(fs.resolve(0, "a/e").unwrap(), "../../d"), Ok(2));
+    assert_eq!(
+        fs.render_du(),
+        "584\t/a/e\n94853\t/a\n24933642\t/d\n48381165\t/\n"
+    );
+    assert!(fs
+        .render_tree()
+        .starts_with("- / (dir)\n  - a (dir)\n    - e (dir)\n      - i (file, size=584)\n"));
+    assert_eq!(fs.get_deletion_size(70000000, 30000000), 24933642);
+    assert_eq!(fs.get_deletion_size(80000000, 30000000), 0);
+}
+
+#[test]
+fn inconsistent_transcripts() {
+    let lines = |s: &str| s.lines().map(String::from).collect::<Vec<String>>();
+    assert_eq!(
+        FileSystem::from_transcript(&lines("$ cd /\n$ cd a")).unwrap_err(),
+        TranscriptError::UnknownDirectory(2, String::from("a"))
+    );
+    assert_eq!(
+        FileSystem::from_transcript(&lines("$ cd ..")).unwrap_err(),
+        TranscriptError::AboveRoot(1)
+    );
+    assert_eq!(
+        FileSystem::from_transcript(&lines("$ ls\n1 a\n2 a")).unwrap_err(),
+        TranscriptError::ConflictingEntry(3, String::from("a"))
+    );
+    assert_eq!(
+        FileSystem::from_transcript(&lines("$ cd /\n1 a")).unwrap_err(),
+        TranscriptError::OutputWithoutListing(2)
+    );
+}
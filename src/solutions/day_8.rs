@@ -0,0 +1,90 @@
+pub struct Forest {
+    pub width: usize,
+    pub height: usize,
+    pub trees: Vec<u8>,
+}
+
+impl Forest {
+    pub fn parse(lines: &[String]) -> Forest {
+        let width = lines[0].len();
+        let trees = lines
+            .iter()
+            .flat_map(|line| line.bytes().map(|b| b - b'0'))
+            .collect::<Vec<u8>>();
+        assert_eq!(
+            trees.len(),
+            width * lines.len(),
+            "Forest must be rectangular"
+        );
+        Forest {
+            width,
+            height: lines.len(),
+            trees,
+        }
+    }
+
+    // Visibility from outside and scenic score of every tree, in row-major order.
+    pub fn get_views(&self) -> (Vec<bool>, Vec<usize>) {
+        let mut visible = vec![false; self.trees.len()];
+        let mut scores = vec![1; self.trees.len()];
+        for y in 0..self.height {
+            let row = (0..self.width).map(|x| y * self.width + x);
+            self.sweep(row.clone(), &mut visible, &mut scores);
+            self.sweep(row.rev(), &mut visible, &mut scores);
+        }
+        for x in 0..self.width {
+            let column = (0..self.height).map(|y| y * self.width + x);
+            self.sweep(column.clone(), &mut visible, &mut scores);
+            self.sweep(column.rev(), &mut visible, &mut scores);
+        }
+        (visible, scores)
+    }
+
+    // Looks back along a line of trees. The stack only keeps trees that are not
+    // shorter than a later one, so its top is the tree blocking the view.
+    fn sweep(&self, line: impl Iterator<Item = usize>, visible: &mut [bool], scores: &mut [usize]) {
+        let mut stack: Vec<(usize, u8)> = Vec::new();
+        for (step, index) in line.enumerate() {
+            let tree = self.trees[index];
+            while stack.last().is_some_and(|&(_, other)| other < tree) {
+                stack.pop();
+            }
+            match stack.last() {
+                Some(&(blocker, _)) => scores[index] *= step - blocker,
+                None => {
+                    visible[index] = true;
+                    scores[index] *= step;
+                }
+            }
+            stack.push((step, tree));
+        }
+    }
+
+    pub fn render_heatmap(&self, scores: &[usize]) -> String {
+        let shades = b" .:-=+*#%@";
+        let max = scores.iter().copied().max().unwrap_or(0).max(1);
+        scores
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|score| shades[score * (shades.len() - 1) / max] as char)
+                    .chain(std::iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn example() {
+    let lines = ["30373", "25512", "65332", "33549", "35390"]
+        .iter()
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>();
+    let forest = Forest::parse(&lines);
+    let (visible, scores) = forest.get_views();
+    assert_eq!(visible.iter().filter(|v| **v).count(), 21);
+    assert_eq!(scores[forest.width + 2], 4);
+    assert_eq!(scores[3 * forest.width + 2], 8);
+    assert_eq!(scores.iter().max(), Some(&8));
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+pub struct Rope {
+    pub knots: Vec<(i64, i64)>,
+    pub visited: Vec<HashSet<(i64, i64)>>,
+}
+
+impl Rope {
+    pub fn new(knot_count: usize) -> Rope {
+        assert!(knot_count > 0, "A rope needs at least one knot");
+        Rope {
+            knots: vec![(0, 0); knot_count],
+            visited: vec![HashSet::from([(0, 0)]); knot_count],
+        }
+    }
+
+    pub fn step(&mut self, direction: char) {
+        let head = &mut self.knots[0];
+        match direction {
+            'U' => head.1 += 1,
+            'D' => head.1 -= 1,
+            'R' => head.0 += 1,
+            'L' => head.0 -= 1,
+            _ => panic!("Invalid direction"),
+        }
+        self.visited[0].insert(self.knots[0]);
+        for i in 1..self.knots.len() {
+            let (leader, knot) = (self.knots[i - 1], self.knots[i]);
+            let (dx, dy) = (leader.0 - knot.0, leader.1 - knot.1);
+            if dx.abs() <= 1 && dy.abs() <= 1 {
+                break;
+            }
+            self.knots[i] = (knot.0 + dx.signum(), knot.1 + dy.signum());
+            self.visited[i].insert(self.knots[i]);
+        }
+    }
+
+    pub fn apply(&mut self, line: &str) {
+        let (direction, steps) = line.split_once(' ').unwrap();
+        let direction = direction.chars().next().unwrap();
+        for _ in 0..steps.parse::<usize>().unwrap() {
+            self.step(direction);
+        }
+    }
+
+    pub fn get_tail_visited(&self) -> usize {
+        self.visited.last().unwrap().len()
+    }
+
+    // Draws the knots over the tail's trail, with `s` marking the start.
+    pub fn render(&self) -> String {
+        let cells = self.visited.iter().flatten().chain(self.knots.iter());
+        let min_x = cells.clone().map(|c| c.0).min().unwrap();
+        let max_x = cells.clone().map(|c| c.0).max().unwrap();
+        let min_y = cells.clone().map(|c| c.1).min().unwrap();
+        let max_y = cells.map(|c| c.1).max().unwrap();
+        let tail = self.visited.last().unwrap();
+        let mut output = String::new();
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                let knot = self.knots.iter().position(|k| *k == (x, y));
+                output.push(match knot {
+                    Some(0) => 'H',
+                    Some(i) => std::char::from_digit(i as u32 % 36, 36).unwrap(),
+                    None if (x, y) == (0, 0) => 's',
+                    None if tail.contains(&(x, y)) => '#',
+                    None => '.',
+                });
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+// Renders the rope after every line of movements.
+pub fn get_frames(lines: &[String], knot_count: usize) -> Vec<String> {
+    let mut rope = Rope::new(knot_count);
+    lines
+        .iter()
+        .map(|line| {
+            rope.apply(line);
+            rope.render()
+        })
+        .collect()
+}
+
+#[test]
+fn example() {
+    let lines = ["R 5", "U 8", "L 8", "D 3", "R 17", "D 10", "L 25", "U 20"];
+    let mut rope = Rope::new(10);
+    for line in lines {
+        rope.apply(line);
+    }
+    assert_eq!(rope.get_tail_visited(), 36);
+
+    let frames = get_frames(&[String::from("R 4"), String::from("U 1")], 2);
+    assert_eq!(frames[0], "s##1H\n");
+    assert_eq!(frames[1], "....H\ns##1.\n");
+}
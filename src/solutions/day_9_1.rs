@@ -1,39 +1,13 @@
-use crate::utils::files::{get_data_as_lines, split2};
+use crate::solutions::day_9::Rope;
+use crate::utils::files::get_data_as_lines;
 
 pub fn solve() -> String {
     let lines = get_data_as_lines("day_9_rope.txt");
-
-    let mut tail = (0, 0);
-    let mut head = (0, 0);
-    let mut history = vec![(0, 0)];
-
+    let mut rope = Rope::new(2);
     for line in lines {
-        let (direction, movements) = split2(line, " ");
-        for _ in 0..movements {
-            head = match direction {
-                'U' => (head.0, head.1 + 1),
-                'D' => (head.0, head.1 - 1),
-                'R' => (head.0 + 1, head.1),
-                'L' => (head.0 - 1, head.1),
-                _ => panic!("Invalid direction"),
-            };
-
-            if head.0 > tail.0 + 1 {
-                tail = (tail.0 + 1, head.1);
-            } else if head.0 < tail.0 - 1 {
-                tail = (tail.0 - 1, head.1);
-            } else if head.1 > tail.1 + 1 {
-                tail = (head.0, tail.1 + 1);
-            } else if head.1 < tail.1 - 1 {
-                tail = (head.0, tail.1 - 1);
-            }
-            history.push(tail);
-        }
+        rope.apply(&line);
     }
-
-    history.sort();
-    history.dedup();
-    history.len().to_string()
+    rope.get_tail_visited().to_string()
 }
 
 #[test]
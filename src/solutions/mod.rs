@@ -1,29 +1,42 @@
+pub mod day_10;
 pub mod day_10_1;
 pub mod day_10_2;
+pub mod day_11;
 pub mod day_11_1;
 pub mod day_11_2;
+pub mod day_12;
 pub mod day_12_1;
 pub mod day_12_2;
+pub mod day_13;
 pub mod day_13_1;
 pub mod day_13_2;
+pub mod day_14;
 pub mod day_14_1;
 pub mod day_14_2;
+pub mod day_15;
 pub mod day_15_1;
 pub mod day_15_2;
+pub mod day_16;
 pub mod day_16_1;
 pub mod day_16_2;
+pub mod day_17;
 pub mod day_17_1;
 pub mod day_17_2;
+pub mod day_18;
 pub mod day_18_1;
 pub mod day_18_2;
+pub mod day_19;
 pub mod day_19_1;
 pub mod day_19_2;
 pub mod day_1_1;
 pub mod day_1_2;
+pub mod day_20;
 pub mod day_20_1;
 pub mod day_20_2;
+pub mod day_21;
 pub mod day_21_1;
 pub mod day_21_2;
+pub mod day_22;
 pub mod day_22_1;
 pub mod day_22_2;
 pub mod day_23_1;
@@ -39,11 +52,15 @@ pub mod day_4_1;
 pub mod day_4_2;
 pub mod day_5_1;
 pub mod day_5_2;
+pub mod day_6;
 pub mod day_6_1;
 pub mod day_6_2;
+pub mod day_7;
 pub mod day_7_1;
 pub mod day_7_2;
+pub mod day_8;
 pub mod day_8_1;
 pub mod day_8_2;
+pub mod day_9;
 pub mod day_9_1;
 pub mod day_9_2;
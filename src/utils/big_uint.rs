@@ -0,0 +1,114 @@
+use std::fmt;
+
+// Arbitrary-precision unsigned integer, stored as little-endian 64-bit limbs
+// without trailing zero limbs.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    pub fn from_u64(value: u64) -> BigUint {
+        let mut result = BigUint { limbs: vec![value] };
+        result.normalize();
+        result
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 64 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u128;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let a = *self.limbs.get(i).unwrap_or(&0) as u128;
+            let b = *other.limbs.get(i).unwrap_or(&0) as u128;
+            let sum = a + b + carry;
+            limbs.push(sum as u64);
+            carry = sum >> 64;
+        }
+        limbs.push(carry as u64);
+        let mut result = BigUint { limbs };
+        result.normalize();
+        result
+    }
+
+    pub fn multiply(&self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let current = limbs[i + j] as u128 + *a as u128 * *b as u128 + carry;
+                limbs[i + j] = current as u64;
+                carry = current >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        let mut result = BigUint { limbs };
+        result.normalize();
+        result
+    }
+
+    // Divides by a single limb, returning the quotient and the remainder.
+    pub fn divide_u64(&self, divisor: u64) -> (BigUint, u64) {
+        assert!(divisor != 0, "Division by zero");
+        let mut limbs = vec![0u64; self.limbs.len()];
+        let mut remainder = 0u128;
+        for i in (0..self.limbs.len()).rev() {
+            let current = (remainder << 64) | self.limbs[i] as u128;
+            limbs[i] = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        let mut quotient = BigUint { limbs };
+        quotient.normalize();
+        (quotient, remainder as u64)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut chunks = Vec::new();
+        let mut current = self.clone();
+        while !current.is_zero() {
+            let (quotient, remainder) = current.divide_u64(10_000_000_000_000_000_000);
+            chunks.push(remainder);
+            current = quotient;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:019}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn arithmetic() {
+    let a = BigUint::from_u64(u64::MAX);
+    let square = a.multiply(&a);
+    assert_eq!(
+        square.to_string(),
+        "340282366920938463426481119284349108225"
+    );
+    assert_eq!(square.bits(), 128);
+    let sum = square.add(&BigUint::from_u64(7));
+    assert_eq!(sum.divide_u64(1_000_000_007).1, 114_944_276);
+    assert_eq!(sum.divide_u64(u64::MAX).0, a);
+}
@@ -8,6 +8,11 @@ pub fn get_data_as_lines(file: &str) -> Vec<String> {
     reader.lines().map(|l| l.unwrap()).collect()
 }
 
+pub fn get_data_as_reader(file: &str) -> BufReader<File> {
+    let path = Path::new("./data").join(file);
+    BufReader::new(File::open(path).unwrap())
+}
+
 pub fn split2<T, U>(s: String, pat: &str) -> (T, U)
 where
     T: std::str::FromStr,
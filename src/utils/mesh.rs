@@ -0,0 +1,208 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::utils::voxel::{Voxel, VoxelSet, FACES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Faces {
+    Exposed,
+    Exterior,
+}
+
+// Triangles wound counter-clockwise when seen from outside.
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub vertices: Vec<[i32; 3]>,
+    pub triangles: Vec<([usize; 3], [i32; 3])>,
+}
+
+fn get_coordinate(v: &Voxel, axis: usize) -> i32 {
+    match axis {
+        0 => v.x,
+        1 => v.y,
+        _ => v.z,
+    }
+}
+
+impl Mesh {
+    fn add_vertex(&mut self, indices: &mut HashMap<[i32; 3], usize>, vertex: [i32; 3]) -> usize {
+        *indices.entry(vertex).or_insert_with(|| {
+            self.vertices.push(vertex);
+            self.vertices.len() - 1
+        })
+    }
+
+    // Adds the rectangle covering `size` cells from `corner` in the plane
+    // `axis = plane`, facing `sign`.
+    fn add_rectangle(
+        &mut self,
+        indices: &mut HashMap<[i32; 3], usize>,
+        (axis, sign, plane): (usize, i32, i32),
+        corner: (i32, i32),
+        size: (i32, i32),
+    ) {
+        let (u, w) = ((axis + 1) % 3, (axis + 2) % 3);
+        let mut corners = [(0, 0), (size.0, 0), size, (0, size.1)].map(|(du, dw)| {
+            let mut vertex = [0; 3];
+            vertex[axis] = plane;
+            vertex[u] = corner.0 + du;
+            vertex[w] = corner.1 + dw;
+            self.add_vertex(indices, vertex)
+        });
+        if sign < 0 {
+            corners.reverse();
+        }
+        let mut normal = [0; 3];
+        normal[axis] = sign;
+        self.triangles
+            .push(([corners[0], corners[1], corners[2]], normal));
+        self.triangles
+            .push(([corners[0], corners[2], corners[3]], normal));
+    }
+
+    // One quad per visible face, or with `merge` set, coplanar faces combined
+    // greedily into larger rectangles.
+    pub fn from_voxels(set: &VoxelSet, faces: Faces, merge: bool) -> Mesh {
+        let exterior = match faces {
+            Faces::Exterior => Some(set.get_exterior()),
+            Faces::Exposed => None,
+        };
+        let mut planes: BTreeMap<(usize, i32, i32), Vec<(i32, i32)>> = BTreeMap::new();
+        for voxel in set.iter() {
+            for (i, face) in FACES.iter().enumerate() {
+                let neighbour = voxel.offset(*face);
+                let visible = match &exterior {
+                    Some(exterior) => exterior.contains(&neighbour),
+                    None => !set.contains(&neighbour),
+                };
+                if !visible {
+                    continue;
+                }
+                let (axis, sign) = (i / 2, if i % 2 == 0 { -1 } else { 1 });
+                let plane = get_coordinate(&voxel, axis) + (sign + 1) / 2;
+                planes.entry((axis, sign, plane)).or_default().push((
+                    get_coordinate(&voxel, (axis + 1) % 3),
+                    get_coordinate(&voxel, (axis + 2) % 3),
+                ));
+            }
+        }
+
+        let mut mesh = Mesh::default();
+        let mut indices = HashMap::new();
+        for (key, mut cells) in planes {
+            if !merge {
+                for cell in cells {
+                    mesh.add_rectangle(&mut indices, key, cell, (1, 1));
+                }
+                continue;
+            }
+            cells.sort_unstable_by_key(|(u, w)| (*w, *u));
+            let mut remaining = cells.iter().copied().collect::<HashSet<(i32, i32)>>();
+            for (u, w) in cells {
+                if !remaining.contains(&(u, w)) {
+                    continue;
+                }
+                let mut width = 1;
+                while remaining.contains(&(u + width, w)) {
+                    width += 1;
+                }
+                let mut height = 1;
+                while (u..u + width).all(|x| remaining.contains(&(x, w + height))) {
+                    height += 1;
+                }
+                for dw in 0..height {
+                    for du in 0..width {
+                        remaining.remove(&(u + du, w + dw));
+                    }
+                }
+                mesh.add_rectangle(&mut indices, key, (u, w), (width, height));
+            }
+        }
+        mesh
+    }
+
+    pub fn to_obj(&self) -> String {
+        let mut obj = String::new();
+        for v in self.vertices.iter() {
+            obj.push_str(&format!("v {} {} {}\n", v[0], v[1], v[2]));
+        }
+        let mut normals: Vec<[i32; 3]> = Vec::new();
+        for (triangle, normal) in self.triangles.iter() {
+            let n = match normals.iter().position(|x| x == normal) {
+                Some(n) => n,
+                None => {
+                    obj.push_str(&format!("vn {} {} {}\n", normal[0], normal[1], normal[2]));
+                    normals.push(*normal);
+                    normals.len() - 1
+                }
+            };
+            obj.push_str(&format!(
+                "f {}//{} {}//{} {}//{}\n",
+                triangle[0] + 1,
+                n + 1,
+                triangle[1] + 1,
+                n + 1,
+                triangle[2] + 1,
+                n + 1
+            ));
+        }
+        obj
+    }
+
+    pub fn to_stl(&self, name: &str) -> String {
+        let mut stl = format!("solid {}\n", name);
+        for (triangle, normal) in self.triangles.iter() {
+            stl.push_str(&format!(
+                "  facet normal {} {} {}\n    outer loop\n",
+                normal[0], normal[1], normal[2]
+            ));
+            for i in triangle {
+                let v = self.vertices[*i];
+                stl.push_str(&format!("      vertex {} {} {}\n", v[0], v[1], v[2]));
+            }
+            stl.push_str("    endloop\n  endfacet\n");
+        }
+        stl.push_str(&format!("endsolid {}\n", name));
+        stl
+    }
+}
+
+#[test]
+fn boxes() {
+    let cube = VoxelSet::from_voxels(&[Voxel::new(0, 0, 0)]);
+    let mesh = Mesh::from_voxels(&cube, Faces::Exposed, false);
+    assert_eq!((mesh.vertices.len(), mesh.triangles.len()), (8, 12));
+    for (triangle, normal) in mesh.triangles.iter() {
+        let [a, b, c] = triangle.map(|i| mesh.vertices[i]);
+        let (e1, e2) = (
+            [0, 1, 2].map(|k| b[k] - a[k]),
+            [0, 1, 2].map(|k| c[k] - a[k]),
+        );
+        let cross = [
+            e1[1] * e2[2] - e1[2] * e2[1],
+            e1[2] * e2[0] - e1[0] * e2[2],
+            e1[0] * e2[1] - e1[1] * e2[0],
+        ];
+        assert_eq!(cross, *normal);
+    }
+
+    let bar = VoxelSet::from_voxels(&[Voxel::new(0, 0, 0), Voxel::new(1, 0, 0)]);
+    assert_eq!(
+        Mesh::from_voxels(&bar, Faces::Exposed, false)
+            .triangles
+            .len(),
+        20
+    );
+    let merged = Mesh::from_voxels(&bar, Faces::Exposed, true);
+    assert_eq!(merged.triangles.len(), 12);
+    assert_eq!(
+        merged
+            .to_obj()
+            .lines()
+            .filter(|l| l.starts_with("f "))
+            .count(),
+        12
+    );
+    let stl = merged.to_stl("bar");
+    assert!(stl.starts_with("solid bar\n") && stl.ends_with("endsolid bar\n"));
+    assert_eq!(stl.matches("facet normal").count(), 12);
+}
@@ -1 +1,7 @@
+pub mod big_uint;
 pub mod files;
+pub mod mesh;
+pub mod ocr;
+pub mod random;
+pub mod rational;
+pub mod voxel;
@@ -0,0 +1,63 @@
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+const FONT: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+fn get_glyph(pixels: &[bool], width: usize, left: usize) -> String {
+    (0..GLYPH_HEIGHT)
+        .flat_map(|y| (left..left + GLYPH_WIDTH).map(move |x| (x, y)))
+        .map(|(x, y)| if pixels[y * width + x] { '#' } else { '.' })
+        .collect()
+}
+
+// Reads the capital letters drawn in the 4x6 font, with one blank column
+// between letters. Unrecognised glyphs are returned as `?`.
+pub fn decode(pixels: &[bool], width: usize) -> String {
+    assert!(
+        pixels.len() >= width * GLYPH_HEIGHT,
+        "Screen is too short for the font"
+    );
+    (0..(width + 1) / (GLYPH_WIDTH + 1))
+        .map(|i| {
+            let glyph = get_glyph(pixels, width, i * (GLYPH_WIDTH + 1));
+            FONT.iter()
+                .find(|(_, pattern)| *pattern == glyph)
+                .map_or('?', |(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[test]
+fn letters() {
+    let rows = [
+        "#..#.####.###..",
+        "#..#.#....#..#.",
+        "####.###..#..#.",
+        "#..#.#....###..",
+        "#..#.#....#.#..",
+        "#..#.####.#..#.",
+    ];
+    let pixels = rows
+        .iter()
+        .flat_map(|row| row.chars().map(|c| c == '#'))
+        .collect::<Vec<bool>>();
+    assert_eq!(decode(&pixels, 15), "HER");
+}
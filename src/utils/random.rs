@@ -0,0 +1,45 @@
+// Small deterministic xorshift generator for randomised tests and tie-breaking
+// priorities. Not suitable for anything that needs real randomness.
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    pub fn new(seed: u64) -> XorShift {
+        // An all-zero state would stay zero forever.
+        XorShift {
+            state: if seed == 0 {
+                0x2545_f491_4f6c_dd1d
+            } else {
+                seed
+            },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    // Uniform-ish value in `0..limit`.
+    pub fn below(&mut self, limit: u64) -> u64 {
+        self.next_u64() % limit
+    }
+
+    // Value in the inclusive range `low..=high`.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+}
+
+#[test]
+fn ranges() {
+    let mut rng = XorShift::new(0);
+    assert_ne!(rng.next_u64(), 0);
+    for _ in 0..1000 {
+        assert!(rng.below(5) < 5);
+        assert!((-3..=3).contains(&rng.between(-3, 3)));
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+// Exact fraction in lowest terms with a positive denominator. Arithmetic is
+// checked and returns None on overflow or division by zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    pub fn new(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Some(Rational {
+            numerator: numerator.checked_div(divisor)?,
+            denominator: denominator.checked_div(divisor)?,
+        })
+    }
+
+    pub fn from_integer(value: i64) -> Rational {
+        Rational {
+            numerator: value as i128,
+            denominator: 1,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn to_integer(self) -> Option<i64> {
+        if self.denominator != 1 {
+            return None;
+        }
+        self.numerator.try_into().ok()
+    }
+
+    pub fn add(&self, other: &Rational) -> Option<Rational> {
+        let divisor = gcd(self.denominator, other.denominator);
+        let scale = other.denominator / divisor;
+        let numerator = self
+            .numerator
+            .checked_mul(scale)?
+            .checked_add(other.numerator.checked_mul(self.denominator / divisor)?)?;
+        Rational::new(numerator, self.denominator.checked_mul(scale)?)
+    }
+
+    pub fn negate(&self) -> Option<Rational> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    pub fn subtract(&self, other: &Rational) -> Option<Rational> {
+        self.add(&other.negate()?)
+    }
+
+    pub fn multiply(&self, other: &Rational) -> Option<Rational> {
+        // Cross-cancelling first keeps intermediate values small.
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        Rational::new(
+            (self.numerator / a).checked_mul(other.numerator / b)?,
+            (self.denominator / b).checked_mul(other.denominator / a)?,
+        )
+    }
+
+    pub fn divide(&self, other: &Rational) -> Option<Rational> {
+        if other.is_zero() {
+            return None;
+        }
+        self.multiply(&Rational::new(other.denominator, other.numerator)?)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[test]
+fn arithmetic() {
+    let half = Rational::new(2, -4).unwrap();
+    assert_eq!(half.to_string(), "-1/2");
+    let third = Rational::new(1, 3).unwrap();
+    assert_eq!(half.add(&third).unwrap().to_string(), "-1/6");
+    assert_eq!(half.multiply(&third).unwrap().to_string(), "-1/6");
+    assert_eq!(half.divide(&third).unwrap().to_string(), "-3/2");
+    assert_eq!(third.subtract(&third).unwrap(), Rational::ZERO);
+    assert_eq!(half.divide(&Rational::ZERO), None);
+    let big = Rational::from_integer(i64::MAX);
+    assert_eq!(big.multiply(&big).unwrap().to_integer(), None);
+    assert_eq!(Rational::new(6, 3).unwrap().to_integer(), Some(2));
+}
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub struct Voxel {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+pub const FACES: [(i32, i32, i32); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+impl Voxel {
+    pub fn new(x: i32, y: i32, z: i32) -> Voxel {
+        Voxel { x, y, z }
+    }
+
+    pub fn offset(&self, (dx, dy, dz): (i32, i32, i32)) -> Voxel {
+        Voxel::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+
+    pub fn get_neighbours(&self) -> impl Iterator<Item = Voxel> + '_ {
+        FACES.iter().map(|face| self.offset(*face))
+    }
+}
+
+// A set of voxels within fixed inclusive bounds, stored as a dense bitset.
+#[derive(Debug, Clone)]
+pub struct VoxelSet {
+    pub min: Voxel,
+    pub max: Voxel,
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl VoxelSet {
+    pub fn new(min: Voxel, max: Voxel) -> VoxelSet {
+        let volume = ((max.x - min.x + 1) * (max.y - min.y + 1) * (max.z - min.z + 1)) as usize;
+        VoxelSet {
+            min,
+            max,
+            bits: vec![0; volume.div_ceil(64)],
+            len: 0,
+        }
+    }
+
+    pub fn from_voxels(voxels: &[Voxel]) -> VoxelSet {
+        assert!(!voxels.is_empty(), "Cannot bound an empty set of voxels");
+        let min = Voxel::new(
+            voxels.iter().map(|v| v.x).min().unwrap(),
+            voxels.iter().map(|v| v.y).min().unwrap(),
+            voxels.iter().map(|v| v.z).min().unwrap(),
+        );
+        let max = Voxel::new(
+            voxels.iter().map(|v| v.x).max().unwrap(),
+            voxels.iter().map(|v| v.y).max().unwrap(),
+            voxels.iter().map(|v| v.z).max().unwrap(),
+        );
+        let mut set = VoxelSet::new(min, max);
+        for voxel in voxels {
+            set.insert(*voxel);
+        }
+        set
+    }
+
+    pub fn in_bounds(&self, v: &Voxel) -> bool {
+        (self.min.x..=self.max.x).contains(&v.x)
+            && (self.min.y..=self.max.y).contains(&v.y)
+            && (self.min.z..=self.max.z).contains(&v.z)
+    }
+
+    fn index(&self, v: &Voxel) -> usize {
+        let width = (self.max.x - self.min.x + 1) as usize;
+        let depth = (self.max.y - self.min.y + 1) as usize;
+        ((v.z - self.min.z) as usize * depth + (v.y - self.min.y) as usize) * width
+            + (v.x - self.min.x) as usize
+    }
+
+    pub fn contains(&self, v: &Voxel) -> bool {
+        if !self.in_bounds(v) {
+            return false;
+        }
+        let index = self.index(v);
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    // Returns false if the voxel was already present.
+    pub fn insert(&mut self, v: Voxel) -> bool {
+        assert!(self.in_bounds(&v), "{:?} is out of bounds", v);
+        let index = self.index(&v);
+        let bit = 1 << (index % 64);
+        if self.bits[index / 64] & bit != 0 {
+            return false;
+        }
+        self.bits[index / 64] |= bit;
+        self.len += 1;
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get_bounds(&self) -> impl Iterator<Item = Voxel> {
+        let (min, max) = (self.min, self.max);
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Voxel::new(x, y, z)))
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Voxel> + '_ {
+        self.get_bounds().filter(|v| self.contains(v))
+    }
+
+    // Every face not shared with another voxel in the set.
+    pub fn get_surface_area(&self) -> usize {
+        self.iter()
+            .flat_map(|v| FACES.map(|face| v.offset(face)))
+            .filter(|n| !self.contains(n))
+            .count()
+    }
+
+    // Air reachable from outside, found by flood filling from a corner of the
+    // bounding box grown by one in every direction.
+    pub fn get_exterior(&self) -> VoxelSet {
+        let min = self.min.offset((-1, -1, -1));
+        let mut exterior = VoxelSet::new(min, self.max.offset((1, 1, 1)));
+        self.fill(&mut exterior, min);
+        exterior
+    }
+
+    fn fill(&self, region: &mut VoxelSet, start: Voxel) {
+        let mut queue = VecDeque::from([start]);
+        region.insert(start);
+        while let Some(voxel) = queue.pop_front() {
+            for n in voxel.get_neighbours() {
+                if region.in_bounds(&n) && !self.contains(&n) && region.insert(n) {
+                    queue.push_back(n);
+                }
+            }
+        }
+    }
+
+    pub fn get_exterior_surface_area(&self) -> usize {
+        let exterior = self.get_exterior();
+        self.iter()
+            .flat_map(|v| FACES.map(|face| v.offset(face)))
+            .filter(|n| exterior.contains(n))
+            .count()
+    }
+
+    // Enclosed regions of air, each as its own set.
+    pub fn get_air_pockets(&self) -> Vec<VoxelSet> {
+        let exterior = self.get_exterior();
+        let mut seen = VoxelSet::new(self.min, self.max);
+        let mut pockets = Vec::new();
+        for voxel in self.get_bounds() {
+            if self.contains(&voxel) || exterior.contains(&voxel) || seen.contains(&voxel) {
+                continue;
+            }
+            let mut pocket = VoxelSet::new(self.min, self.max);
+            self.fill(&mut pocket, voxel);
+            for v in pocket.iter() {
+                seen.insert(v);
+            }
+            pockets.push(pocket);
+        }
+        pockets
+    }
+}
+
+#[test]
+fn hollow_cube() {
+    let shell = (0..3)
+        .flat_map(|z| (0..3).flat_map(move |y| (0..3).map(move |x| Voxel::new(x, y, z))))
+        .filter(|v| *v != Voxel::new(1, 1, 1))
+        .collect::<Vec<Voxel>>();
+    let set = VoxelSet::from_voxels(&shell);
+    assert_eq!(set.len(), 26);
+    assert_eq!(set.get_exterior_surface_area(), 54);
+    assert_eq!(set.get_surface_area(), 60);
+    let pockets = set.get_air_pockets();
+    assert_eq!(pockets.len(), 1);
+    assert_eq!(
+        pockets[0].iter().collect::<Vec<Voxel>>(),
+        vec![Voxel::new(1, 1, 1)]
+    );
+}